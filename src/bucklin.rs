@@ -0,0 +1,159 @@
+use std::hash::Hash;
+
+use crate::{Decision, ElectionRule, Outcome, Stake, TieBreak, TieBreaker, Vote};
+
+/// Bucklin voting: first preferences are counted, then second preferences
+/// added to them, and so on until some candidate is ranked by a majority of
+/// the voters, the candidate with the most votes at that level winning.
+///
+/// Truncated ballots stop adding votes past their last ranked candidate but
+/// still count towards the majority. A ballot tying candidates across a level
+/// gives each a share of the positions the tie spans within it. Empty ballots
+/// abstain. If nobody reaches a majority at the last level, the candidate
+/// with the most votes there wins.
+#[derive(Clone, Debug)]
+pub struct Bucklin<T> {
+    /// The Grand Junction system: after first and second preferences, every
+    /// other ranked candidate is added at once, and the highest total wins.
+    pub grand_junction: bool,
+    /// Settles ties for the most votes at the deciding level.
+    pub tie_breaker: TieBreaker<T>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BucklinCount<'a, T> {
+    /// Votes of every candidate at each level counted, highest first.
+    pub levels: Vec<Vec<(&'a T, f64)>>,
+    /// Half of the voters, which a candidate must exceed to win.
+    pub majority: f64,
+    /// `None` if no ballot counts.
+    pub winner: Option<&'a T>,
+    pub tie_breaks: Vec<TieBreak<'a, T>>,
+}
+
+impl<T> Bucklin<T> {
+    pub fn new(tie_breaker: TieBreaker<T>) -> Self {
+        Bucklin { grand_junction: false, tie_breaker }
+    }
+
+    pub fn grand_junction(mut self, grand_junction: bool) -> Self {
+        self.grand_junction = grand_junction;
+        self
+    }
+}
+
+/// Votes of every candidate, indexed by id, counting the first `depth`
+/// positions of each ballot.
+fn ranked_within<T: Eq + Hash>(vote: &Vote<T>, depth: usize) -> Vec<f64> {
+    let mut votes = vec![0.; vote.candidates().len()];
+    for (voters, ballot) in vote.ballots() {
+        let mut position = 0;
+        for class in ballot.iter() {
+            if position >= depth {
+                break;
+            }
+            let within = depth.min(position + class.len()) - position;
+            let share = *voters as f64 * within as f64 / class.len() as f64;
+            class.iter().for_each(|id| votes[id.index()] += share);
+            position += class.len();
+        }
+    }
+    votes
+}
+
+impl<T: Eq + Hash> Bucklin<T> {
+    pub fn count<'a>(&self, vote: &'a Vote<T>) -> BucklinCount<'a, T> {
+        let n = vote.candidates().len();
+        let voters: usize = vote.ballots().iter().filter(|(_, b)| !b.is_empty()).map(|(v, _)| v).sum();
+        let mut count = BucklinCount { levels: vec![], majority: voters as f64 / 2., winner: None, tie_breaks: vec![] };
+        if voters == 0 {
+            return count;
+        }
+        let depths: Vec<usize> = if self.grand_junction && n > 3 { vec![1, 2, n] } else { (1..=n).collect() };
+        for (level, &depth) in depths.iter().enumerate() {
+            let votes = ranked_within(vote, depth);
+            let mut tally: Vec<(&T, f64)> = vote.ids().map(|id| (vote.candidate(id), votes[id.index()])).collect();
+            tally.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+            let best = tally[0].1;
+            let leaders: Vec<&T> = tally.iter()
+                .filter(|(_, v)| crate::same_score(*v, best))
+                .map(|(c, _)| *c)
+                .collect();
+            count.levels.push(tally);
+            if best > count.majority || level == depths.len() - 1 {
+                count.winner = Some(if leaders.len() == 1 {
+                    leaders[0]
+                } else {
+                    let history = &count.levels[..count.levels.len() - 1];
+                    let tie_break = self.tie_breaker.break_tie(&leaders, Stake::Win, history);
+                    let winner = tie_break.chosen;
+                    count.tie_breaks.push(tie_break);
+                    winner
+                });
+                break;
+            }
+        }
+        count
+    }
+}
+
+impl<T: Eq + Hash> ElectionRule<T> for Bucklin<T> {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        let mut count = self.count(vote);
+        Decision {
+            outcome: count.winner.map_or(Outcome::NoWinner, Outcome::Winner),
+            scores: count.levels.pop(),
+            tie_breaks: count.tie_breaks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::tennessee;
+    use crate::{Bucklin, PreOrder, TieBreaker, Vote};
+
+    #[test]
+    fn bucklin() {
+        let vote = tennessee();
+        let count = Bucklin::new(TieBreaker::Lot(0)).count(&vote);
+        assert_eq!(count.levels.len(), 2);
+        assert_eq!(
+            count.levels[1],
+            vec![(&"Nashville", 68.), (&"Chattanooga", 58.), (&"Memphis", 42.), (&"Knoxville", 32.)]
+        );
+        assert_eq!(count.winner, Some(&"Nashville"));
+    }
+
+    #[test]
+    fn truncated_ballots_and_grand_junction() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c", "d"])
+            .ballot(2, vec!["b"])
+            .ballot(5, vec!["c", "d", "b", "a"])
+            .ballot(5, vec!["a"])
+            .build()
+            .unwrap();
+        let count = Bucklin::new(TieBreaker::Lot(0)).count(&vote);
+        assert_eq!(count.majority, 6.);
+        assert_eq!(count.levels.len(), 3);
+        assert_eq!(count.levels[2][0], (&"b", 7.));
+        assert_eq!(count.winner, Some(&"b"));
+
+        let count = Bucklin::new(TieBreaker::Lot(0)).grand_junction(true).count(&vote);
+        assert_eq!(count.levels[2][0], (&"a", 10.));
+        assert_eq!(count.winner, Some(&"a"));
+
+        // A tie across the first two positions counts half at the first level.
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(2, PreOrder::weak(vec![vec!["a", "b"], vec!["c"]]))
+            .ballot(1, vec!["c"])
+            .build()
+            .unwrap();
+        let count = Bucklin::new(TieBreaker::Lot(0)).count(&vote);
+        assert_eq!(count.levels[0], vec![(&"a", 1.), (&"b", 1.), (&"c", 1.)]);
+        assert_eq!(count.levels[1], vec![(&"a", 2.), (&"b", 2.), (&"c", 1.)]);
+        assert_eq!(count.tie_breaks.len(), 1);
+    }
+}
@@ -0,0 +1,254 @@
+use std::hash::Hash;
+
+use crate::irv::first_preferences;
+use crate::majority::smith_among;
+use crate::{
+    condorcet_winner_among, CandidateId, Condorcet, Decision, ElectionRule, Outcome, PositionalScoring, Round,
+    RunoffCount, Stake, TieBreaker, Vote,
+};
+
+/// Elects the Condorcet winner if there is one, otherwise whoever `fallback`
+/// elects.
+#[derive(Clone, Debug)]
+pub struct CondorcetCompletion<R> {
+    pub fallback: R,
+}
+
+impl<R> CondorcetCompletion<R> {
+    pub fn new(fallback: R) -> Self {
+        CondorcetCompletion { fallback }
+    }
+}
+
+impl CondorcetCompletion<PositionalScoring> {
+    /// Black's method, falling back to the Borda count.
+    pub fn black() -> Self {
+        CondorcetCompletion::new(PositionalScoring::Borda)
+    }
+}
+
+impl<T: Eq + Hash + Clone, R: ElectionRule<T>> ElectionRule<T> for CondorcetCompletion<R> {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        match vote.condorcet_winner() {
+            Some(winner) => Decision::new(Outcome::Winner(winner), None),
+            None => self.fallback.elect(vote),
+        }
+    }
+}
+
+/// Benham's method: instant-runoff rounds until one of the continuing
+/// candidates beats all the others head to head.
+#[derive(Clone, Debug)]
+pub struct Benham<T> {
+    /// Picks who is eliminated among candidates tied last.
+    pub tie_breaker: TieBreaker<T>,
+}
+
+/// Tideman's alternative method: every round, the candidates outside the
+/// Smith set of the continuing ones are dropped, then the one with the fewest
+/// first preferences is eliminated, until a single candidate is left.
+///
+/// Candidates dropped from the Smith set are listed first among the
+/// eliminated of the round they are dropped in, which is counted without
+/// them, the last round included.
+#[derive(Clone, Debug)]
+pub struct TidemanAlternative<T> {
+    /// Picks who is eliminated among candidates tied last.
+    pub tie_breaker: TieBreaker<T>,
+}
+
+/// Woodall's method: candidates are eliminated in instant-runoff order until
+/// a single member of the Smith set is left, who wins.
+#[derive(Clone, Debug)]
+pub struct Woodall<T> {
+    /// Picks who is eliminated among candidates tied last.
+    pub tie_breaker: TieBreaker<T>,
+}
+
+/// Instant-runoff rounds without the majority stop. Before each round,
+/// `select` may narrow down the continuing candidates, who are then listed
+/// first among the round's eliminated, or name the winner.
+fn runoff<'a, T: Eq + Hash, F>(vote: &'a Vote<T>, tie_breaker: &TieBreaker<T>, mut select: F) -> RunoffCount<'a, T>
+    where F: FnMut(&mut Vec<CandidateId>) -> Option<CandidateId>
+{
+    let mut continuing: Vec<CandidateId> = vote.ids().collect();
+    let mut count = RunoffCount { rounds: vec![], winner: None, tie_breaks: vec![] };
+    loop {
+        let before = continuing.clone();
+        let winner = select(&mut continuing);
+        let dropped = before.into_iter().filter(|id| !continuing.contains(id)).map(|id| vote.candidate(id)).collect();
+        let mut mask = vec![false; vote.candidates().len()];
+        continuing.iter().for_each(|id| mask[id.index()] = true);
+        let (votes, exhausted) = first_preferences(vote, &mask);
+        let mut tally: Vec<(&T, f64)> = continuing.iter().map(|&id| (vote.candidate(id), votes[id.index()])).collect();
+        tally.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        let mut round = Round { tally, exhausted, eliminated: dropped };
+        if let Some(winner) = winner.or_else(|| Some(continuing[0]).filter(|_| continuing.len() == 1)) {
+            count.winner = Some(vote.candidate(winner));
+            count.rounds.push(round);
+            return count;
+        }
+        if votes.iter().sum::<f64>() == 0. {
+            count.rounds.push(round);
+            return count;
+        }
+
+        let lowest = round.tally[round.tally.len() - 1].1;
+        let last: Vec<&T> = round.tally.iter()
+            .filter(|(_, v)| crate::same_score(*v, lowest))
+            .map(|(c, _)| *c)
+            .collect();
+        let loser = if last.len() == 1 {
+            last[0]
+        } else {
+            let history: Vec<_> = count.rounds.iter().map(|r| r.tally.clone()).collect();
+            let tie_break = tie_breaker.break_tie(&last, Stake::Elimination, &history);
+            let loser = tie_break.chosen;
+            count.tie_breaks.push(tie_break);
+            loser
+        };
+        continuing.retain(|&id| vote.candidate(id) != loser);
+        round.eliminated.push(loser);
+        count.rounds.push(round);
+    }
+}
+
+impl<T> Benham<T> {
+    pub fn new(tie_breaker: TieBreaker<T>) -> Self {
+        Benham { tie_breaker }
+    }
+}
+
+impl<T: Eq + Hash> Benham<T> {
+    pub fn count<'a>(&self, vote: &'a Vote<T>) -> RunoffCount<'a, T> {
+        let matrix = vote.pairwise_matrix();
+        runoff(vote, &self.tie_breaker, |continuing| condorcet_winner_among(&matrix, continuing))
+    }
+}
+
+impl<T> TidemanAlternative<T> {
+    pub fn new(tie_breaker: TieBreaker<T>) -> Self {
+        TidemanAlternative { tie_breaker }
+    }
+}
+
+impl<T: Eq + Hash> TidemanAlternative<T> {
+    pub fn count<'a>(&self, vote: &'a Vote<T>) -> RunoffCount<'a, T> {
+        let beats = vote.majority_graph();
+        runoff(vote, &self.tie_breaker, |continuing| {
+            let indices: Vec<usize> = continuing.iter().map(|id| id.index()).collect();
+            let smith = smith_among(&beats, &indices);
+            continuing.retain(|id| smith.contains(&id.index()));
+            None
+        })
+    }
+}
+
+impl<T> Woodall<T> {
+    pub fn new(tie_breaker: TieBreaker<T>) -> Self {
+        Woodall { tie_breaker }
+    }
+}
+
+impl<T: Eq + Hash> Woodall<T> {
+    pub fn count<'a>(&self, vote: &'a Vote<T>) -> RunoffCount<'a, T> {
+        let all: Vec<usize> = vote.ids().map(|id| id.index()).collect();
+        let smith = smith_among(&vote.majority_graph(), &all);
+        runoff(vote, &self.tie_breaker, |continuing| {
+            let mut left = continuing.iter().filter(|id| smith.contains(&id.index()));
+            match (left.next(), left.next()) {
+                (Some(&last), None) => Some(last),
+                _ => None,
+            }
+        })
+    }
+}
+
+fn decide<T>(mut count: RunoffCount<T>) -> Decision<T> {
+    let last = count.rounds.pop().unwrap();
+    Decision {
+        outcome: count.winner.map_or(Outcome::NoWinner, Outcome::Winner),
+        scores: Some(last.tally),
+        tie_breaks: count.tie_breaks,
+    }
+}
+
+impl<T: Eq + Hash> ElectionRule<T> for Benham<T> {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        decide(self.count(vote))
+    }
+}
+
+impl<T: Eq + Hash> ElectionRule<T> for TidemanAlternative<T> {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        decide(self.count(vote))
+    }
+}
+
+impl<T: Eq + Hash> ElectionRule<T> for Woodall<T> {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        decide(self.count(vote))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::tennessee;
+    use crate::{
+        Benham, CondorcetCompletion, ElectionRule, InstantRunoff, Outcome, Plurality, RunoffCount, TidemanAlternative,
+        TieBreaker, Vote, Woodall,
+    };
+
+    #[test]
+    fn condorcet_completion() {
+        let vote = tennessee();
+        let decision = CondorcetCompletion::new(Plurality).elect(&vote);
+        assert_eq!(decision.outcome, Outcome::Winner(&"Nashville"));
+        assert_eq!(decision.scores, None);
+
+        // a, b and c form a cycle, and all of them beat d.
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c", "d"])
+            .ballot(4, vec!["a", "b", "c", "d"])
+            .ballot(3, vec!["b", "c", "a", "d"])
+            .ballot(3, vec!["c", "a", "b", "d"])
+            .build()
+            .unwrap();
+        let black = CondorcetCompletion::black().elect(&vote);
+        assert_eq!(black.outcome, Outcome::Winner(&"a"));
+        assert_eq!(black.scores.unwrap()[0], (&"a", 21.));
+    }
+
+    #[test]
+    fn instant_runoff_hybrids() {
+        // c is the Condorcet loser despite the most first preferences. The
+        // Smith set is a, b and d: b beats a, which beats d, which beats b.
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c", "d"])
+            .ballot(3, vec!["c", "d", "b", "a"])
+            .ballot(6, vec!["d", "b", "a", "c"])
+            .ballot(7, vec!["b", "a", "d", "c"])
+            .ballot(9, vec!["c", "a", "d", "b"])
+            .build()
+            .unwrap();
+        fn eliminated<'a>(count: &RunoffCount<'a, &'static str>) -> Vec<Vec<&'a &'static str>> {
+            count.rounds.iter().map(|r| r.eliminated.clone()).collect()
+        }
+
+        let irv = InstantRunoff::new(TieBreaker::Lot(0)).count(&vote);
+        assert_eq!(irv.winner, Some(&"b"));
+
+        let benham = Benham::new(TieBreaker::Lot(0)).count(&vote);
+        assert_eq!(eliminated(&benham), vec![vec![&"a"], vec![]]);
+        assert_eq!(benham.winner, Some(&"d"));
+
+        let tideman = TidemanAlternative::new(TieBreaker::Lot(0)).count(&vote);
+        assert_eq!(eliminated(&tideman), vec![vec![&"c", &"b"], vec![&"d"]]);
+        assert_eq!(tideman.rounds[0].tally, vec![(&"a", 9.), (&"d", 9.), (&"b", 7.)]);
+        assert_eq!(tideman.winner, Some(&"a"));
+
+        let woodall = Woodall::new(TieBreaker::Lot(0)).count(&vote);
+        assert_eq!(eliminated(&woodall), vec![vec![&"a"], vec![&"d"], vec![]]);
+        assert_eq!(woodall.winner, Some(&"b"));
+    }
+}
@@ -0,0 +1,157 @@
+use std::hash::Hash;
+
+use crate::irv::first_preferences;
+use crate::{CandidateId, Decision, ElectionRule, Outcome, Stake, TieBreak, TieBreaker, Vote};
+
+/// Coombs' method: like instant-runoff voting, ballots count for their most
+/// preferred continuing candidate until one holds a majority of them, but the
+/// candidate eliminated each round is the one ranked last by the most voters.
+///
+/// The candidates a ballot leaves out share its last place, or its lowest
+/// ranked continuing candidates if it ranks all of them. Ballots without any
+/// continuing candidate left are exhausted and count for neither.
+#[derive(Clone, Debug)]
+pub struct Coombs<T> {
+    /// Picks who is eliminated among candidates with the most last places.
+    pub tie_breaker: TieBreaker<T>,
+}
+
+/// One round of a Coombs count.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoombsRound<'a, T> {
+    /// First preferences of every continuing candidate, highest first.
+    pub tally: Vec<(&'a T, f64)>,
+    /// Last places of every continuing candidate, most first.
+    pub last_places: Vec<(&'a T, f64)>,
+    /// Votes of the ballots with no continuing candidate left.
+    pub exhausted: f64,
+    /// The candidate eliminated at the end of the round, `None` in the last.
+    pub eliminated: Option<&'a T>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoombsCount<'a, T> {
+    pub rounds: Vec<CoombsRound<'a, T>>,
+    /// `None` if no ballot is left to count before anyone won.
+    pub winner: Option<&'a T>,
+    pub tie_breaks: Vec<TieBreak<'a, T>>,
+}
+
+impl<T> Coombs<T> {
+    pub fn new(tie_breaker: TieBreaker<T>) -> Self {
+        Coombs { tie_breaker }
+    }
+}
+
+/// Last places of every candidate of `continuing`, indexed by id.
+fn last_places<T: Eq + Hash>(vote: &Vote<T>, continuing: &[bool]) -> Vec<f64> {
+    let mut places = vec![0.; continuing.len()];
+    for (voters, ballot) in vote.ballots() {
+        let ranked: Vec<Vec<CandidateId>> = ballot.iter()
+            .map(|class| class.iter().cloned().filter(|id| continuing[id.index()]).collect::<Vec<_>>())
+            .filter(|class| !class.is_empty())
+            .collect();
+        if ranked.is_empty() {
+            continue;
+        }
+        let unranked: Vec<CandidateId> = vote.ids()
+            .filter(|id| continuing[id.index()] && !ranked.iter().flatten().any(|r| r == id))
+            .collect();
+        let last = if unranked.is_empty() { &ranked[ranked.len() - 1] } else { &unranked };
+        let share = *voters as f64 / last.len() as f64;
+        last.iter().for_each(|id| places[id.index()] += share);
+    }
+    places
+}
+
+impl<T: Eq + Hash> Coombs<T> {
+    pub fn count<'a>(&self, vote: &'a Vote<T>) -> CoombsCount<'a, T> {
+        let mut continuing = vec![true; vote.candidates().len()];
+        let mut count = CoombsCount { rounds: vec![], winner: None, tie_breaks: vec![] };
+        loop {
+            let (votes, exhausted) = first_preferences(vote, &continuing);
+            let places = last_places(vote, &continuing);
+            let sorted = |values: &[f64]| {
+                let mut standing: Vec<(&T, f64)> = vote.ids()
+                    .filter(|id| continuing[id.index()])
+                    .map(|id| (vote.candidate(id), values[id.index()]))
+                    .collect();
+                standing.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+                standing
+            };
+            let mut round = CoombsRound { tally: sorted(&votes), last_places: sorted(&places), exhausted, eliminated: None };
+            let active: f64 = votes.iter().sum();
+            let (leader, best) = round.tally[0];
+            if round.tally.len() == 1 || best > active / 2. {
+                count.winner = Some(leader);
+                count.rounds.push(round);
+                return count;
+            }
+            if active == 0. {
+                count.rounds.push(round);
+                return count;
+            }
+
+            let most = round.last_places[0].1;
+            let last: Vec<&T> = round.last_places.iter()
+                .filter(|(_, p)| crate::same_score(*p, most))
+                .map(|(c, _)| *c)
+                .collect();
+            let loser = if last.len() == 1 {
+                last[0]
+            } else {
+                let history: Vec<_> = count.rounds.iter().map(|r| r.tally.clone()).collect();
+                let tie_break = self.tie_breaker.break_tie(&last, Stake::Elimination, &history);
+                let loser = tie_break.chosen;
+                count.tie_breaks.push(tie_break);
+                loser
+            };
+            continuing[vote.id_of(loser).unwrap().index()] = false;
+            round.eliminated = Some(loser);
+            count.rounds.push(round);
+        }
+    }
+}
+
+impl<T: Eq + Hash> ElectionRule<T> for Coombs<T> {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        let mut count = self.count(vote);
+        let last = count.rounds.pop().unwrap();
+        Decision {
+            outcome: count.winner.map_or(Outcome::NoWinner, Outcome::Winner),
+            scores: Some(last.tally),
+            tie_breaks: count.tie_breaks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::tennessee;
+    use crate::{Coombs, TieBreaker, Vote};
+
+    #[test]
+    fn coombs() {
+        let vote = tennessee();
+        let count = Coombs::new(TieBreaker::Lot(0)).count(&vote);
+        assert_eq!(count.rounds[0].last_places, vec![(&"Memphis", 58.), (&"Knoxville", 42.), (&"Nashville", 0.), (&"Chattanooga", 0.)]);
+        assert_eq!(count.rounds[0].eliminated, Some(&"Memphis"));
+        assert_eq!(count.rounds[1].tally[0], (&"Nashville", 68.));
+        assert_eq!(count.winner, Some(&"Nashville"));
+    }
+
+    #[test]
+    fn left_out_candidates_share_the_last_place() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(4, vec!["a", "b"])
+            .ballot(3, vec!["b"])
+            .ballot(2, vec!["c", "b", "a"])
+            .build()
+            .unwrap();
+        let count = Coombs::new(TieBreaker::Lot(0)).count(&vote);
+        assert_eq!(count.rounds[0].last_places, vec![(&"c", 5.5), (&"a", 3.5), (&"b", 0.)]);
+        assert_eq!(count.rounds[1].tally, vec![(&"b", 5.), (&"a", 4.)]);
+        assert_eq!(count.winner, Some(&"b"));
+    }
+}
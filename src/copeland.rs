@@ -0,0 +1,144 @@
+use std::hash::Hash;
+
+use crate::{Decision, ElectionRule, PreOrder, SocialWelfareFunction, Tally, Vote};
+
+/// Copeland's method: a point for every pairwise victory and `alpha` points
+/// for every pairwise tie, the highest score winning.
+///
+/// The default, `alpha = 0.5`, is Copeland's original rule. Llull's rule
+/// (`alpha = 1`) rewards ties as victories.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Copeland {
+    alpha: f64,
+}
+
+impl Default for Copeland {
+    fn default() -> Self {
+        Copeland { alpha: 0.5 }
+    }
+}
+
+impl Copeland {
+    /// Panics unless `alpha` lies between 0 and 1.
+    pub fn new(alpha: f64) -> Self {
+        assert!((0. ..=1.).contains(&alpha), "Copeland's alpha must lie between 0 and 1, got {}", alpha);
+        Copeland { alpha }
+    }
+
+    pub fn llull() -> Self {
+        Copeland { alpha: 1. }
+    }
+
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+}
+
+/// A candidate's pairwise results.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record<'a, T> {
+    pub candidate: &'a T,
+    pub wins: usize,
+    pub losses: usize,
+    pub ties: usize,
+    pub score: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CopelandCount<'a, T> {
+    /// Every candidate's record, in declaration order.
+    pub records: Vec<Record<'a, T>>,
+    pub tally: Tally<'a, T>,
+}
+
+impl Copeland {
+    pub fn count<'a, T: Eq + Hash>(&self, vote: &'a Vote<T>) -> CopelandCount<'a, T> {
+        let matrix = vote.pairwise_matrix();
+        let records: Vec<Record<T>> = vote.ids()
+            .map(|i| {
+                let mut record = Record { candidate: vote.candidate(i), wins: 0, losses: 0, ties: 0, score: 0. };
+                for j in vote.ids().filter(|&j| j != i) {
+                    let (for_i, for_j) = (matrix[[i.index(), j.index()]], matrix[[j.index(), i.index()]]);
+                    if for_i > for_j {
+                        record.wins += 1;
+                    } else if for_i < for_j {
+                        record.losses += 1;
+                    } else {
+                        record.ties += 1;
+                    }
+                }
+                record.score = record.wins as f64 + self.alpha * record.ties as f64;
+                record
+            })
+            .collect();
+        let tally = Tally::new(records.iter().map(|r| (r.candidate, r.score)).collect(), vote.has_preferences());
+        CopelandCount { records, tally }
+    }
+}
+
+impl<T: Eq + Hash> ElectionRule<T> for Copeland {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        self.count(vote).tally.into()
+    }
+}
+
+impl<T: Eq + Hash> SocialWelfareFunction<T> for Copeland {
+    fn rank<'a>(&self, vote: &'a Vote<T>) -> PreOrder<&'a T> {
+        PreOrder::from_scores(&self.count(vote).tally.scores)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::tennessee;
+    use crate::{Copeland, ElectionRule, Outcome, PreOrder, Record, SocialWelfareFunction, Vote};
+
+    #[test]
+    fn copeland() {
+        let vote = tennessee();
+        assert_eq!(
+            Copeland::default().rank(&vote),
+            PreOrder::from(vec![&"Nashville", &"Chattanooga", &"Knoxville", &"Memphis"])
+        );
+        assert_eq!(
+            Copeland::default().count(&vote).records[0],
+            Record { candidate: &"Memphis", wins: 0, losses: 3, ties: 0, score: 0. }
+        );
+    }
+
+    #[test]
+    fn pairwise_ties_score_alpha() {
+        // b beats a and c but loses to d, which ties a and c.
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c", "d"])
+            .ballot(1, vec!["d", "b", "c", "a"])
+            .ballot(1, vec!["d", "b", "a", "c"])
+            .ballot(1, vec!["b", "c", "a", "d"])
+            .ballot(1, vec!["a", "c", "d", "b"])
+            .build()
+            .unwrap();
+        let records = Copeland::default().count(&vote).records;
+        assert_eq!((records[1].wins, records[1].losses, records[1].ties), (2, 1, 0));
+        assert_eq!((records[3].wins, records[3].losses, records[3].ties), (1, 0, 2));
+        assert_eq!(Copeland::default().count(&vote).tally.winners, vec![&"b", &"d"]);
+        assert_eq!(Copeland::new(0.).count(&vote).tally.winners, vec![&"b"]);
+        assert_eq!(Copeland::llull().count(&vote).tally.winners, vec![&"d"]);
+    }
+
+    #[test]
+    fn every_contest_tied() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b"])
+            .ballot(1, vec!["a", "b"])
+            .ballot(1, vec!["b", "a"])
+            .build()
+            .unwrap();
+        assert_eq!(Copeland::new(0.).elect(&vote).outcome, Outcome::Tie(vec![&"a", &"b"]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Copeland's alpha must lie between 0 and 1")]
+    fn alpha_must_be_a_fraction() {
+        Copeland::new(f64::NAN);
+    }
+}
@@ -0,0 +1,335 @@
+use std::hash::Hash;
+
+use ndarray::Array2;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, ToPrimitive, Zero};
+
+use crate::tournament::maximize;
+use crate::{CandidateId, Decision, ElectionRule, Outcome, PreOrder, SocialWelfareFunction, Vote};
+
+/// How Dodgson scores are computed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum DodgsonMode {
+    /// Solves an integer program over how many voters of each distinct ballot
+    /// raise the candidate how far, by branch and bound. Computing Dodgson
+    /// scores is NP-hard and the program grows with the candidates and the
+    /// distinct ballots, so elections with more than
+    /// [`Dodgson::MAX_EXACT_CANDIDATES`] candidates or
+    /// [`Dodgson::MAX_EXACT_BALLOTS`] distinct ballots are scored with
+    /// [`DodgsonMode::Quick`] instead.
+    Exact,
+    /// Tideman's approximation: the sum of the margins of the contests the
+    /// candidate loses.
+    Tideman,
+    /// Dodgson Quick: the swaps needed against each opponent, as if every swap
+    /// could be made right below it. A lower bound of the exact score, often
+    /// equal to it.
+    Quick,
+}
+
+/// Dodgson's rule: elects the candidates needing the fewest swaps of adjacent
+/// candidates on ballots to become the Condorcet winner.
+///
+/// A swap moves the candidate above one candidate ranked right above it, or
+/// tied with it. Candidates a ballot leaves out are tied below the ranked
+/// ones, so raising a candidate left out first takes it above them. Empty
+/// ballots abstain and are never changed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Dodgson {
+    pub mode: DodgsonMode,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DodgsonCount<'a, T> {
+    /// Every candidate's score in declaration order, the lower the better.
+    /// `None` if no swap can make it win, when all ballots are empty, which
+    /// only exact scores detect.
+    pub scores: Vec<(&'a T, Option<usize>)>,
+    pub winners: Vec<&'a T>,
+    /// Whether the scores are exact, which they are in exact mode unless the
+    /// election is too large for it.
+    pub exact: bool,
+}
+
+/// How much `x`'s head-to-head votes against each candidate, indexed by id,
+/// must grow relative to the votes for them to beat them all.
+fn deficits(matrix: &Array2<usize>, x: usize) -> Vec<usize> {
+    (0..matrix.nrows())
+        .map(|y| if y == x { 0 } else { (matrix[[y, x]] + 1).saturating_sub(matrix[[x, y]]) })
+        .collect()
+}
+
+/// The distinct non-empty ballots, with how many voters cast each.
+fn distinct_ballots<T: Eq + Hash>(vote: &Vote<T>) -> Vec<(usize, &PreOrder<CandidateId>)> {
+    let mut distinct: Vec<(usize, &PreOrder<CandidateId>)> = vec![];
+    for (voters, ballot) in vote.ballots().iter().filter(|(_, ballot)| !ballot.is_empty()) {
+        match distinct.iter_mut().find(|(_, b)| *b == ballot) {
+            Some((total, _)) => *total += voters,
+            None => distinct.push((*voters, ballot)),
+        }
+    }
+    distinct
+}
+
+/// A constraint `coefficients · m >= bound` on the number of voters `m` of
+/// each ballot group making each move.
+type Row = (Vec<BigRational>, BigRational);
+
+/// Raising the candidate past `opponent` on the ballots of a group, as many
+/// times as voters make the `moves` listed.
+struct Pass {
+    opponent: CandidateId,
+    gain: usize,
+    moves: Vec<usize>,
+}
+
+/// The integer program giving the exact score of a candidate: the fewest swaps
+/// `costs · m` meeting `rows`, with `m` whole.
+///
+/// A voter raises the candidate past every opponent of some levels of its
+/// ballot, counted up from the candidate, then possibly past some opponents of
+/// the next level. The moves of a ballot group are how many of its voters stop
+/// after each level, and how many of those raise the candidate past each
+/// opponent with a deficit of the next level. Overtaking an opponent tied with
+/// the candidate gains 1 against it, an opponent above 2.
+struct Program {
+    costs: Vec<usize>,
+    rows: Vec<Row>,
+    passes: Vec<Pass>,
+}
+
+fn integer(n: i64) -> BigRational {
+    BigRational::from_integer(BigInt::from(n))
+}
+
+fn program<T: Eq + Hash>(vote: &Vote<T>, ballots: &[(usize, &PreOrder<CandidateId>)], x: CandidateId, deficits: &[usize]) -> Program {
+    let mut costs: Vec<usize> = vec![];
+    let mut passes: Vec<Pass> = vec![];
+    // Constraints `terms >= bound`, as sparse terms.
+    let mut rows: Vec<(Vec<(usize, i64)>, i64)> = vec![];
+    for &(voters, ballot) in ballots {
+        let (tied, above): (Vec<CandidateId>, &[Vec<CandidateId>]) = match ballot.rank_of(&x) {
+            Some(rank) => (ballot[rank].iter().cloned().filter(|&c| c != x).collect(), &ballot[..rank]),
+            None => (vote.ids().filter(|&c| c != x && ballot.rank_of(&c).is_none()).collect(), &ballot[..]),
+        };
+        let mut levels: Vec<(Vec<CandidateId>, usize)> = Some((tied, 1)).into_iter()
+            .chain(above.iter().rev().map(|class| (class.clone(), 2)))
+            .filter(|(level, _)| !level.is_empty())
+            .collect();
+        let useful = levels.iter().rposition(|(level, _)| level.iter().any(|c| deficits[c.index()] > 0));
+        levels.truncate(useful.map_or(0, |l| l + 1));
+        if levels.is_empty() {
+            continue;
+        }
+        // stops[k]: the voters stopping after the first `k + 1` levels.
+        let mut cost = 0;
+        let stops: Vec<usize> = levels.iter()
+            .map(|(level, _)| {
+                cost += level.len();
+                costs.push(cost);
+                costs.len() - 1
+            })
+            .collect();
+        // Partial moves past the first level, which any voter can make.
+        let mut first = vec![];
+        for (l, (level, gain)) in levels.iter().enumerate() {
+            for &c in level {
+                let mut moves = stops[l..].to_vec();
+                if level.len() > 1 && deficits[c.index()] > 0 {
+                    costs.push(1);
+                    let partial = costs.len() - 1;
+                    moves.push(partial);
+                    // Only the voters stopping right below the level raise
+                    // the candidate past part of it.
+                    match l {
+                        0 => first.push(partial),
+                        _ => rows.push((vec![(stops[l - 1], 1), (partial, -1)], 0)),
+                    }
+                }
+                passes.push(Pass { opponent: c, gain: *gain, moves });
+            }
+        }
+        // The voters stopping after some level, plus those raising the
+        // candidate past part of the first one, are at most all of them.
+        let stopping: Vec<(usize, i64)> = stops.iter().map(|&s| (s, -1)).collect();
+        let bound = -(voters as i64);
+        if first.is_empty() {
+            rows.push((stopping.clone(), bound));
+        }
+        for partial in first {
+            rows.push((stopping.iter().cloned().chain(Some((partial, -1))).collect(), bound));
+        }
+    }
+    for y in (0..deficits.len()).filter(|&y| deficits[y] > 0) {
+        let gains: Vec<(usize, i64)> = passes.iter()
+            .filter(|p| p.opponent.index() == y)
+            .flat_map(|p| p.moves.iter().map(move |&m| (m, p.gain as i64)))
+            .collect();
+        // Gaining at most 2 per swap, whole swaps need half the deficit
+        // rounded up, which the relaxation would otherwise miss.
+        rows.push((gains.iter().map(|&(m, _)| (m, 1)).collect(), deficits[y].div_ceil(2) as i64));
+        rows.push((gains, deficits[y] as i64));
+    }
+    let n = costs.len();
+    let rows = rows.into_iter()
+        .map(|(terms, bound)| {
+            let mut coefficients = vec![BigRational::zero(); n];
+            for (j, a) in terms {
+                coefficients[j] += integer(a);
+            }
+            (coefficients, integer(bound))
+        })
+        .collect();
+    Program { costs, rows, passes }
+}
+
+/// The least cost of `program` with whole moves, if any, by branch and bound.
+/// Each relaxation is solved exactly through its dual.
+fn minimize(program: &Program) -> Option<usize> {
+    let n = program.costs.len();
+    let costs: Vec<BigRational> = program.costs.iter().map(|&c| integer(c as i64)).collect();
+    let mut best: Option<usize> = None;
+    let mut nodes: Vec<Vec<Row>> = vec![vec![]];
+    while let Some(branch) = nodes.pop() {
+        let rows: Vec<&Row> = program.rows.iter().chain(&branch).collect();
+        let constraints: Vec<Vec<BigRational>> = (0..n).map(|j| rows.iter().map(|(a, _)| a[j].clone()).collect()).collect();
+        let objective = rows.iter().map(|(_, b)| b.clone()).collect();
+        let (value, moves) = match maximize(&constraints, &costs, objective) {
+            Some(solution) => solution,
+            None => continue,
+        };
+        // Rounding up how many times the candidate passes each opponent keeps
+        // the constraints met, giving a whole solution to improve on.
+        let rounded = program.passes.iter()
+            .map(|pass| pass.moves.iter().map(|&m| &moves[m]).sum::<BigRational>().ceil().to_integer())
+            .sum::<BigInt>()
+            .to_usize()
+            .unwrap();
+        let incumbent = best.map_or(rounded, |best| best.min(rounded));
+        best = Some(incumbent);
+        if value.ceil().to_integer().to_usize().unwrap() >= incumbent {
+            continue;
+        }
+        // A whole solution would have been its own rounding.
+        let j = moves.iter().position(|m| !m.is_integer()).unwrap();
+        let unit = |a: i64| {
+            let mut coefficients = vec![BigRational::zero(); n];
+            coefficients[j] = integer(a);
+            coefficients
+        };
+        let floor = moves[j].floor();
+        let mut down = branch.clone();
+        down.push((unit(-1), -floor.clone()));
+        let mut up = branch;
+        up.push((unit(1), floor + BigRational::one()));
+        nodes.push(up);
+        nodes.push(down);
+    }
+    best
+}
+
+impl Dodgson {
+    /// The most candidates [`DodgsonMode::Exact`] scores exactly.
+    pub const MAX_EXACT_CANDIDATES: usize = 16;
+    /// The most distinct non-empty ballots [`DodgsonMode::Exact`] scores
+    /// exactly.
+    pub const MAX_EXACT_BALLOTS: usize = 32;
+
+    pub fn count<'a, T: Eq + Hash>(&self, vote: &'a Vote<T>) -> DodgsonCount<'a, T> {
+        let matrix = vote.pairwise_matrix();
+        let ballots = distinct_ballots(vote);
+        let exact = self.mode == DodgsonMode::Exact
+            && vote.candidates().len() <= Dodgson::MAX_EXACT_CANDIDATES
+            && ballots.len() <= Dodgson::MAX_EXACT_BALLOTS;
+        let scores: Vec<(&T, Option<usize>)> = vote.ids()
+            .map(|x| {
+                let deficits = deficits(&matrix, x.index());
+                let score = match self.mode {
+                    _ if deficits.iter().all(|&d| d == 0) => Some(0),
+                    DodgsonMode::Exact if exact => minimize(&program(vote, &ballots, x, &deficits)),
+                    DodgsonMode::Tideman => Some(deficits.iter().map(|d| d.saturating_sub(1)).sum()),
+                    DodgsonMode::Exact | DodgsonMode::Quick => Some(deficits.iter().map(|d| d.div_ceil(2)).sum()),
+                };
+                (vote.candidate(x), score)
+            })
+            .collect();
+        let best = scores.iter().filter_map(|&(_, s)| s).min();
+        let winners = scores.iter().filter(|&&(_, s)| s.is_some() && s == best).map(|&(c, _)| c).collect();
+        DodgsonCount { scores, winners, exact }
+    }
+}
+
+impl<T: Eq + Hash> ElectionRule<T> for Dodgson {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        Decision::new(Outcome::from_winners(self.count(vote).winners), None)
+    }
+}
+
+/// Ranks candidates from the lowest score up, those who cannot win last.
+impl<T: Eq + Hash> SocialWelfareFunction<T> for Dodgson {
+    fn rank<'a>(&self, vote: &'a Vote<T>) -> PreOrder<&'a T> {
+        let scores: Vec<(&T, f64)> = self.count(vote)
+            .scores
+            .into_iter()
+            .map(|(c, s)| (c, s.map_or(f64::MIN, |s| -(s as f64))))
+            .collect();
+        PreOrder::from_scores(&scores)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::tennessee;
+    use crate::{Dodgson, DodgsonMode, ElectionRule, Outcome, PreOrder, SocialWelfareFunction, Vote};
+
+    #[test]
+    fn dodgson_scores() {
+        let vote = tennessee();
+        let scores = |mode| Dodgson { mode }.count(&vote).scores.into_iter().map(|(_, s)| s.unwrap()).collect::<Vec<_>>();
+        assert_eq!(scores(DodgsonMode::Exact), vec![27, 0, 19, 53]);
+        assert_eq!(scores(DodgsonMode::Quick), vec![27, 0, 19, 53]);
+        assert_eq!(scores(DodgsonMode::Tideman), vec![48, 0, 36, 102]);
+        assert_eq!(
+            Dodgson { mode: DodgsonMode::Exact }.rank(&vote),
+            PreOrder::from(vec![&"Nashville", &"Chattanooga", &"Memphis", &"Knoxville"])
+        );
+    }
+
+    #[test]
+    fn approximations_can_miss() {
+        // b only ties c, but another candidate stands between them on every
+        // ballot ranking c above b.
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c", "d"])
+            .ballot(1, vec!["c", "d", "b", "a"])
+            .ballot(1, vec!["c", "a", "b", "d"])
+            .ballot(2, vec!["b", "c", "d", "a"])
+            .build()
+            .unwrap();
+        let exact = Dodgson { mode: DodgsonMode::Exact }.count(&vote);
+        assert_eq!(exact.scores[1], (&"b", Some(2)));
+        assert_eq!(exact.scores[2], (&"c", Some(1)));
+        assert_eq!(Dodgson { mode: DodgsonMode::Exact }.elect(&vote).outcome, Outcome::Winner(&"c"));
+        assert_eq!(Dodgson { mode: DodgsonMode::Quick }.elect(&vote).outcome, Outcome::Tie(vec![&"b", &"c"]));
+        assert_eq!(Dodgson { mode: DodgsonMode::Tideman }.elect(&vote).outcome, Outcome::Tie(vec![&"b", &"c"]));
+    }
+
+    #[test]
+    fn large_elections() {
+        // Raising a candidate left out past 0 or 1 first takes it past every
+        // other candidate left out, so the exact scores exceed the Quick ones.
+        let vote = Vote::builder().candidates(0..16).ballot(3, vec![0]).ballot(2, vec![1]).build().unwrap();
+        let count = Dodgson { mode: DodgsonMode::Exact }.count(&vote);
+        assert!(count.exact);
+        assert_eq!(count.scores[..3], [(&0, Some(0)), (&1, Some(15)), (&2, Some(19))]);
+        let quick = Dodgson { mode: DodgsonMode::Quick }.count(&vote);
+        assert!(!quick.exact);
+        assert_eq!(quick.scores[..3], [(&0, Some(0)), (&1, Some(1)), (&2, Some(17))]);
+
+        let vote = Vote::builder().candidates(0..24).ballot(3, vec![0]).ballot(2, vec![1]).build().unwrap();
+        let count = Dodgson { mode: DodgsonMode::Exact }.count(&vote);
+        assert!(!count.exact);
+        assert_eq!(count, Dodgson { mode: DodgsonMode::Quick }.count(&vote));
+    }
+}
@@ -0,0 +1,204 @@
+use std::hash::Hash;
+
+use crate::{Decision, ElectionRule, Outcome, PositionalScoring, Round, Stake, TieBreak, TieBreaker, Vote};
+
+/// Baldwin's method: the candidate with the lowest Borda score is eliminated
+/// and the Borda count run again on the remaining ones, until one is left.
+///
+/// A Condorcet winner always scores above the average, so it is never
+/// eliminated.
+#[derive(Clone, Debug)]
+pub struct Baldwin<T> {
+    /// Picks who is eliminated among candidates tied last.
+    pub tie_breaker: TieBreaker<T>,
+}
+
+/// Nanson's method: every candidate with a Borda score below the average is
+/// eliminated and the Borda count run again on the remaining ones, until
+/// they all have the same score.
+///
+/// A Condorcet winner always scores above the average, so it is never
+/// eliminated.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Nanson;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EliminationCount<'a, T> {
+    /// Borda scores of the continuing candidates each round. Ballots ranking
+    /// none of them abstain and count as exhausted.
+    pub rounds: Vec<Round<'a, T>>,
+    /// The remaining candidates, empty if no ballot is left to count.
+    pub winners: Vec<&'a T>,
+    pub tie_breaks: Vec<TieBreak<'a, T>>,
+}
+
+impl<T> Baldwin<T> {
+    pub fn new(tie_breaker: TieBreaker<T>) -> Self {
+        Baldwin { tie_breaker }
+    }
+}
+
+/// Runs Borda counts among fewer and fewer candidates, `eliminate` choosing
+/// who goes among the continuing ones given the round so far; nobody means
+/// the count is over.
+fn eliminate_until<'a, T: Eq + Hash, F>(vote: &'a Vote<T>, mut eliminate: F) -> EliminationCount<'a, T>
+    where F: FnMut(&Round<'a, T>, &[Round<'a, T>], &mut Vec<TieBreak<'a, T>>) -> Vec<&'a T>
+{
+    let mut continuing = vec![true; vote.candidates().len()];
+    let mut count = EliminationCount { rounds: vec![], winners: vec![], tie_breaks: vec![] };
+    loop {
+        let (scores, exhausted) = PositionalScoring::Borda.scores_among(vote, &continuing);
+        let mut tally: Vec<_> = vote.ids()
+            .filter(|id| continuing[id.index()])
+            .map(|id| (vote.candidate(id), scores[id.index()]))
+            .collect();
+        tally.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        let mut round = Round { tally, exhausted, eliminated: vec![] };
+        if round.tally.iter().all(|(_, s)| *s == 0.) && round.tally.len() > 1 {
+            count.rounds.push(round);
+            return count;
+        }
+        let eliminated = if round.tally.len() == 1 {
+            vec![]
+        } else {
+            eliminate(&round, &count.rounds, &mut count.tie_breaks)
+        };
+        if eliminated.is_empty() {
+            count.winners = round.tally.iter().map(|(c, _)| *c).collect();
+            count.rounds.push(round);
+            return count;
+        }
+        for c in &eliminated {
+            continuing[vote.id_of(c).unwrap().index()] = false;
+        }
+        round.eliminated = eliminated;
+        count.rounds.push(round);
+    }
+}
+
+impl<T: Eq + Hash> Baldwin<T> {
+    pub fn count<'a>(&self, vote: &'a Vote<T>) -> EliminationCount<'a, T> {
+        eliminate_until(vote, |round, previous, tie_breaks| {
+            let lowest = round.tally[round.tally.len() - 1].1;
+            let last: Vec<&T> = round.tally.iter()
+                .filter(|(_, s)| crate::same_score(*s, lowest))
+                .map(|(c, _)| *c)
+                .collect();
+            if last.len() == 1 {
+                return last;
+            }
+            let history: Vec<_> = previous.iter().map(|r| r.tally.clone()).collect();
+            let tie_break = self.tie_breaker.break_tie(&last, Stake::Elimination, &history);
+            let loser = tie_break.chosen;
+            tie_breaks.push(tie_break);
+            vec![loser]
+        })
+    }
+}
+
+impl Nanson {
+    pub fn count<'a, T: Eq + Hash>(&self, vote: &'a Vote<T>) -> EliminationCount<'a, T> {
+        eliminate_until(vote, |round, _, _| {
+            let average = round.tally.iter().map(|(_, s)| s).sum::<f64>() / round.tally.len() as f64;
+            round.tally.iter()
+                .filter(|(_, s)| *s < average && !crate::same_score(*s, average))
+                .map(|(c, _)| *c)
+                .collect()
+        })
+    }
+}
+
+fn decide<T>(mut count: EliminationCount<T>) -> Decision<T> {
+    let last = count.rounds.pop().unwrap();
+    Decision {
+        outcome: Outcome::from_winners(count.winners),
+        scores: Some(last.tally),
+        tie_breaks: count.tie_breaks,
+    }
+}
+
+impl<T: Eq + Hash> ElectionRule<T> for Baldwin<T> {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        decide(self.count(vote))
+    }
+}
+
+impl<T: Eq + Hash> ElectionRule<T> for Nanson {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        decide(self.count(vote))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::tennessee;
+    use crate::{Baldwin, Condorcet, ElectionRule, Nanson, Outcome, PreOrder, TieBreaker, Vote};
+
+    #[test]
+    fn borda_elimination() {
+        let vote = tennessee();
+        let baldwin = Baldwin::new(TieBreaker::Lot(0)).count(&vote);
+        let eliminated: Vec<_> = baldwin.rounds.iter().map(|r| r.eliminated.clone()).collect();
+        assert_eq!(eliminated, vec![vec![&"Knoxville"], vec![&"Memphis"], vec![&"Chattanooga"], vec![]]);
+        assert_eq!(baldwin.rounds[1].tally, vec![(&"Nashville", 126.), (&"Chattanooga", 90.), (&"Memphis", 84.)]);
+        assert_eq!(baldwin.winners, vec![&"Nashville"]);
+
+        let nanson = Nanson.count(&vote);
+        assert_eq!(nanson.rounds[0].tally[0], (&"Nashville", 194.));
+        assert_eq!(nanson.rounds[0].eliminated, vec![&"Memphis", &"Knoxville"]);
+        assert_eq!(nanson.rounds[1].tally, vec![(&"Nashville", 68.), (&"Chattanooga", 32.)]);
+        assert_eq!(nanson.winners, vec![&"Nashville"]);
+    }
+
+    #[test]
+    fn condorcet_winners_are_elected() {
+        let candidates = ["a", "b", "c", "d", "e"];
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut random = |bound: usize| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize % bound
+        };
+        let mut checked = 0;
+        for _ in 0..300 {
+            let mut builder = Vote::builder().candidates(candidates.to_vec());
+            for _ in 0..5 {
+                let mut order = candidates.to_vec();
+                for i in (1..order.len()).rev() {
+                    order.swap(i, random(i + 1));
+                }
+                let mut classes: Vec<Vec<_>> = vec![];
+                for c in order.into_iter().take(1 + random(5)) {
+                    match classes.last_mut() {
+                        Some(class) if random(4) == 0 => class.push(c),
+                        _ => classes.push(vec![c]),
+                    }
+                }
+                builder = builder.ballot(1 + random(4), PreOrder::weak(classes));
+            }
+            let vote = builder.build().unwrap();
+            if let Some(winner) = vote.condorcet_winner() {
+                checked += 1;
+                assert_eq!(Baldwin::new(TieBreaker::Lot(0)).elect(&vote).outcome, Outcome::Winner(winner));
+                assert_eq!(Nanson.elect(&vote).outcome, Outcome::Winner(winner));
+            }
+        }
+        assert!(checked > 100);
+    }
+
+    #[test]
+    fn nanson_ties() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(1, vec!["a", "b", "c"])
+            .ballot(1, vec!["b", "c", "a"])
+            .ballot(1, vec!["c", "a", "b"])
+            .build()
+            .unwrap();
+        assert_eq!(Nanson.elect(&vote).outcome, Outcome::Tie(vec![&"a", &"b", &"c"]));
+        let baldwin = Baldwin::new(TieBreaker::Priority(vec!["a", "b", "c"])).elect(&vote);
+        assert_eq!(baldwin.tie_breaks.len(), 1);
+        assert!(baldwin.outcome.winner().is_some());
+    }
+}
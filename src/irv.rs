@@ -0,0 +1,207 @@
+use std::hash::Hash;
+
+use crate::{CandidateId, Decision, ElectionRule, Outcome, Stake, TieBreak, TieBreaker, Vote};
+
+/// Instant-runoff voting (Hare, alternative vote): every round, ballots count
+/// for their most preferred continuing candidate, and the last candidate is
+/// eliminated until one holds a majority of the ballots still counting.
+///
+/// A ballot tying several continuing candidates first splits evenly between
+/// them. Ballots without any continuing candidate left are exhausted.
+#[derive(Clone, Debug)]
+pub struct InstantRunoff<T> {
+    /// Eliminates at once the lowest candidates whose combined votes are
+    /// fewer than the next candidate's, as none of them can overtake it.
+    pub batch_elimination: bool,
+    /// Picks who is eliminated among candidates tied last.
+    pub tie_breaker: TieBreaker<T>,
+}
+
+/// One round of an instant-runoff count.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Round<'a, T> {
+    /// Votes of every continuing candidate, highest first.
+    pub tally: Vec<(&'a T, f64)>,
+    /// Votes of the ballots with no continuing candidate left.
+    pub exhausted: f64,
+    /// Candidates eliminated at the end of the round, empty in the last one.
+    pub eliminated: Vec<&'a T>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunoffCount<'a, T> {
+    pub rounds: Vec<Round<'a, T>>,
+    /// `None` if no ballot is left to count before anyone won.
+    pub winner: Option<&'a T>,
+    pub tie_breaks: Vec<TieBreak<'a, T>>,
+}
+
+impl<T> InstantRunoff<T> {
+    pub fn new(tie_breaker: TieBreaker<T>) -> Self {
+        InstantRunoff { batch_elimination: false, tie_breaker }
+    }
+
+    pub fn batch_elimination(mut self, batch_elimination: bool) -> Self {
+        self.batch_elimination = batch_elimination;
+        self
+    }
+}
+
+/// Votes of every candidate of `continuing` when each ballot goes to its most
+/// preferred continuing candidates, and the votes of exhausted ballots.
+pub(crate) fn first_preferences<T: Eq + Hash>(vote: &Vote<T>, continuing: &[bool]) -> (Vec<f64>, f64) {
+    let mut votes = vec![0.; continuing.len()];
+    let mut exhausted = 0.;
+    for (voters, ballot) in vote.ballots() {
+        let top = ballot.iter()
+            .map(|class| class.iter().filter(|id| continuing[id.index()]).collect::<Vec<_>>())
+            .find(|class| !class.is_empty());
+        match top {
+            Some(top) => {
+                let share = *voters as f64 / top.len() as f64;
+                top.iter().for_each(|id| votes[id.index()] += share);
+            }
+            None => exhausted += *voters as f64,
+        }
+    }
+    (votes, exhausted)
+}
+
+impl<T: Eq + Hash> InstantRunoff<T> {
+    pub fn count<'a>(&self, vote: &'a Vote<T>) -> RunoffCount<'a, T> {
+        let mut continuing = vec![true; vote.candidates().len()];
+        let mut count = RunoffCount { rounds: vec![], winner: None, tie_breaks: vec![] };
+        loop {
+            let (votes, exhausted) = first_preferences(vote, &continuing);
+            let mut standing: Vec<CandidateId> = vote.ids().filter(|id| continuing[id.index()]).collect();
+            standing.sort_by(|a, b| votes[b.index()].partial_cmp(&votes[a.index()]).unwrap());
+            let active: f64 = standing.iter().map(|id| votes[id.index()]).sum();
+            let mut round = Round {
+                tally: standing.iter().map(|&id| (vote.candidate(id), votes[id.index()])).collect(),
+                exhausted,
+                eliminated: vec![],
+            };
+            let leader = standing[0];
+            if standing.len() == 1 || votes[leader.index()] > active / 2. {
+                count.winner = Some(vote.candidate(leader));
+                count.rounds.push(round);
+                return count;
+            }
+            if active == 0. {
+                count.rounds.push(round);
+                return count;
+            }
+
+            let mut eliminated = if self.batch_elimination {
+                hopeless(&standing, &votes)
+            } else {
+                vec![]
+            };
+            if eliminated.is_empty() {
+                let lowest = votes[standing[standing.len() - 1].index()];
+                let last: Vec<&T> = standing.iter()
+                    .filter(|id| crate::same_score(votes[id.index()], lowest))
+                    .map(|&id| vote.candidate(id))
+                    .collect();
+                let loser = if last.len() == 1 {
+                    last[0]
+                } else {
+                    let history: Vec<_> = count.rounds.iter().map(|r| r.tally.clone()).collect();
+                    let tie_break = self.tie_breaker.break_tie(&last, Stake::Elimination, &history);
+                    let loser = tie_break.chosen;
+                    count.tie_breaks.push(tie_break);
+                    loser
+                };
+                eliminated.push(vote.id_of(loser).unwrap());
+            }
+            for id in &eliminated {
+                continuing[id.index()] = false;
+            }
+            round.eliminated = eliminated.into_iter().map(|id| vote.candidate(id)).collect();
+            count.rounds.push(round);
+        }
+    }
+}
+
+/// The largest group of at least two last candidates, `standing` being sorted
+/// by decreasing votes, whose combined votes are fewer than the votes of the
+/// candidate just above them.
+fn hopeless(standing: &[CandidateId], votes: &[f64]) -> Vec<CandidateId> {
+    let mut below = 0.;
+    let mut group = 0;
+    for (i, id) in standing.iter().enumerate().rev() {
+        below += votes[id.index()];
+        let size = standing.len() - i;
+        if i > 0 && size >= 2 && below < votes[standing[i - 1].index()] {
+            group = size;
+        }
+    }
+    standing[standing.len() - group..].to_vec()
+}
+
+impl<T: Eq + Hash> ElectionRule<T> for InstantRunoff<T> {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        let mut count = self.count(vote);
+        let last = count.rounds.pop().unwrap();
+        Decision {
+            outcome: count.winner.map_or(Outcome::NoWinner, Outcome::Winner),
+            scores: Some(last.tally),
+            tie_breaks: count.tie_breaks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::tennessee;
+    use crate::{InstantRunoff, PreOrder, TieBreaker, Vote};
+
+    #[test]
+    fn instant_runoff() {
+        let vote = tennessee();
+        let count = InstantRunoff::new(TieBreaker::Lot(0)).count(&vote);
+        assert_eq!(count.winner, Some(&"Knoxville"));
+        let eliminated: Vec<_> = count.rounds.iter().map(|r| r.eliminated.clone()).collect();
+        assert_eq!(eliminated, vec![vec![&"Chattanooga"], vec![&"Nashville"], vec![]]);
+        assert_eq!(count.rounds[2].tally, vec![(&"Knoxville", 58.), (&"Memphis", 42.)]);
+    }
+
+    #[test]
+    fn exhausted_ballots_and_batch_elimination() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c", "d"])
+            .ballot(10, vec!["a"])
+            .ballot(8, vec!["b", "a"])
+            .ballot(2, vec!["c"])
+            .ballot(1, PreOrder::weak(vec![vec!["d"], vec!["b", "c"]]))
+            .build()
+            .unwrap();
+        let count = InstantRunoff::new(TieBreaker::Lot(0)).count(&vote);
+        assert_eq!(count.rounds.len(), 3);
+        assert_eq!(count.rounds[1].exhausted, 0.);
+        assert_eq!(count.rounds[1].tally, vec![(&"a", 10.), (&"b", 8.5), (&"c", 2.5)]);
+        assert_eq!(count.rounds[2].tally, vec![(&"a", 10.), (&"b", 9.)]);
+        assert_eq!(count.rounds[2].exhausted, 2.);
+        assert_eq!(count.winner, Some(&"a"));
+
+        let count = InstantRunoff::new(TieBreaker::Lot(0)).batch_elimination(true).count(&vote);
+        assert_eq!(count.rounds[0].eliminated, vec![&"c", &"d"]);
+        assert_eq!(count.rounds[1].tally, vec![(&"a", 10.), (&"b", 9.)]);
+        assert_eq!(count.winner, Some(&"a"));
+    }
+
+    #[test]
+    fn ties_for_last_place_are_broken() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(3, vec!["a"])
+            .ballot(2, vec!["b", "a"])
+            .ballot(2, vec!["c", "b"])
+            .build()
+            .unwrap();
+        let count = InstantRunoff::new(TieBreaker::Priority(vec!["a", "b", "c"])).count(&vote);
+        assert_eq!(count.rounds[0].eliminated, vec![&"c"]);
+        assert_eq!(count.tie_breaks[0].tied, vec![&"b", &"c"]);
+        assert_eq!(count.winner, Some(&"b"));
+    }
+}
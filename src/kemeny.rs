@@ -0,0 +1,292 @@
+use std::hash::Hash;
+
+use crate::{Decision, ElectionRule, Outcome, PositionalScoring, PreOrder, SocialWelfareFunction, Vote};
+
+/// How Kemeny rankings are searched for.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum KemenyMode {
+    /// Dynamic programming over the subsets of candidates, finding every
+    /// optimal ranking. Time and memory grow as `2^n`, so elections with more
+    /// than [`Kemeny::MAX_EXACT_CANDIDATES`] candidates are searched with
+    /// [`KemenyMode::LocalSearch`] instead.
+    Exact,
+    /// Starts from the Borda ranking and moves single candidates up or down
+    /// while it improves the score. Fast, but may end on a local optimum.
+    LocalSearch,
+}
+
+/// Kemeny–Young rank aggregation: the rankings agreeing with the most
+/// pairwise preferences of the voters.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Kemeny {
+    pub mode: KemenyMode,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KemenyCount<'a, T> {
+    /// The first optimal rankings found, without ties, in lexicographic order
+    /// of candidate ids, at most [`Kemeny::MAX_RANKINGS`] of them.
+    pub rankings: Vec<PreOrder<&'a T>>,
+    /// How many optimal rankings were found, listed or not. Elections with
+    /// many symmetries can have a huge number of them.
+    pub total: u64,
+    /// Whether the rankings were searched exactly. Otherwise they come from
+    /// [`KemenyMode::LocalSearch`], chosen or fallen back to, and may not be
+    /// optimal, nor the only ones.
+    pub exact: bool,
+    /// The candidates some optimal ranking puts first, in declaration order.
+    pub winners: Vec<&'a T>,
+    /// The number of voter preferences `a > b` the rankings agree with.
+    pub score: usize,
+}
+
+/// The optimal rankings of candidate indices, with the average position of
+/// every candidate in them.
+struct Solution {
+    orders: Vec<Vec<usize>>,
+    total: u64,
+    exact: bool,
+    firsts: Vec<usize>,
+    positions: Vec<f64>,
+    score: usize,
+}
+
+impl Kemeny {
+    /// The most candidates [`KemenyMode::Exact`] searches exactly.
+    pub const MAX_EXACT_CANDIDATES: usize = 20;
+    /// The most optimal rankings a count lists.
+    pub const MAX_RANKINGS: usize = 1000;
+
+    fn solve<T: Eq + Hash>(&self, vote: &Vote<T>) -> Solution {
+        let matrix = vote.pairwise_matrix();
+        let n = vote.candidates().len();
+        match self.mode {
+            KemenyMode::Exact if n <= Kemeny::MAX_EXACT_CANDIDATES => exact(|i, j| matrix[[i, j]], n),
+            _ => {
+                let borda = PositionalScoring::Borda.tally(vote);
+                let start = borda.scores.iter().map(|(c, _)| vote.id_of(c).unwrap().index()).collect();
+                local_search(|i, j| matrix[[i, j]], start)
+            }
+        }
+    }
+
+    pub fn count<'a, T: Eq + Hash>(&self, vote: &'a Vote<T>) -> KemenyCount<'a, T> {
+        let solution = self.solve(vote);
+        let candidates = vote.candidates();
+        KemenyCount {
+            rankings: solution.orders
+                .into_iter()
+                .map(|order| PreOrder::from(order.into_iter().map(|i| &candidates[i]).collect::<Vec<_>>()))
+                .collect(),
+            total: solution.total,
+            exact: solution.exact,
+            winners: solution.firsts.into_iter().map(|i| &candidates[i]).collect(),
+            score: solution.score,
+        }
+    }
+}
+
+/// The orders of `0..n` maximizing the sum of `prefer(i, j)` over `i` ranked
+/// above `j`, the first [`Kemeny::MAX_RANKINGS`] of them listed, for `n` up to
+/// [`Kemeny::MAX_EXACT_CANDIDATES`].
+fn exact<F: Fn(usize, usize) -> usize>(prefer: F, n: usize) -> Solution {
+    let all = (1usize << n) - 1;
+    // best[s]: the best score of the candidates of `s` ranked on top, in
+    // any order, above the others. last[s]: the candidates of `s` that can
+    // come last among them in such an order.
+    let mut best = vec![0; 1 << n];
+    let mut last = vec![0usize; 1 << n];
+    for s in 1..=all {
+        let mut max = None;
+        for x in (0..n).filter(|x| s & 1 << x != 0) {
+            let gain: usize = (0..n).filter(|y| all & !s & 1 << y != 0).map(|y| prefer(x, y)).sum();
+            let score = best[s & !(1 << x)] + gain;
+            match max {
+                Some(m) if score < m => {}
+                Some(m) if score == m => last[s] |= 1 << x,
+                _ => {
+                    max = Some(score);
+                    last[s] = 1 << x;
+                }
+            }
+        }
+        best[s] = max.unwrap();
+    }
+
+    // above[s]: the optimal orders of `s` on top. below[s]: the ways of
+    // ranking the other candidates under `s` in an optimal order, 0 if `s`
+    // is never on top.
+    let mut above = vec![0u64; 1 << n];
+    above[0] = 1;
+    for s in 1..=all {
+        above[s] = (0..n).filter(|x| last[s] & 1 << x != 0).map(|x| above[s & !(1 << x)]).fold(0, u64::saturating_add);
+    }
+    let mut below = vec![0u64; 1 << n];
+    below[all] = 1;
+    for s in (1..=all).rev() {
+        if below[s] == 0 {
+            continue;
+        }
+        for x in (0..n).filter(|x| last[s] & 1 << x != 0) {
+            below[s & !(1 << x)] = below[s & !(1 << x)].saturating_add(below[s]);
+        }
+    }
+    let total = above[all];
+    // `x` is ranked right under the candidates of `s` in above[s] * below[s | x]
+    // optimal orders.
+    let mut positions = vec![0.; n];
+    for s in (0..all).filter(|&s| below[s] > 0) {
+        for x in (0..n).filter(|x| s & 1 << x == 0 && last[s | 1 << x] & 1 << x != 0) {
+            positions[x] += s.count_ones() as f64 * above[s] as f64 * below[s | 1 << x] as f64 / total as f64;
+        }
+    }
+
+    // Depth first from the top, smallest ids first, so the orders come in
+    // lexicographic order.
+    let mut orders = vec![];
+    let mut stack = vec![(0, vec![])];
+    while let Some((s, top)) = stack.pop() {
+        if orders.len() == Kemeny::MAX_RANKINGS {
+            break;
+        }
+        if s == all {
+            orders.push(top);
+            continue;
+        }
+        for x in (0..n).rev().filter(|x| s & 1 << x == 0 && last[s | 1 << x] & 1 << x != 0 && below[s | 1 << x] > 0) {
+            let mut top = top.clone();
+            top.push(x);
+            stack.push((s | 1 << x, top));
+        }
+    }
+    Solution {
+        orders,
+        total,
+        exact: true,
+        firsts: (0..n).filter(|x| below[1 << x] > 0).collect(),
+        positions,
+        score: best[all],
+    }
+}
+
+fn score<F: Fn(usize, usize) -> usize>(prefer: &F, order: &[usize]) -> usize {
+    order.iter()
+        .enumerate()
+        .flat_map(|(p, &i)| order[p + 1..].iter().map(move |&j| (i, j)))
+        .map(|(i, j)| prefer(i, j))
+        .sum()
+}
+
+/// Moves candidates of `order` to whichever place improves its score most,
+/// until none does.
+fn local_search<F: Fn(usize, usize) -> usize>(prefer: F, mut order: Vec<usize>) -> Solution {
+    let mut current = score(&prefer, &order);
+    loop {
+        let mut improved = None;
+        for from in 0..order.len() {
+            for to in (0..order.len()).filter(|&to| to != from) {
+                let mut moved = order.clone();
+                let candidate = moved.remove(from);
+                moved.insert(to, candidate);
+                let moved_score = score(&prefer, &moved);
+                if moved_score > improved.as_ref().map_or(current, |(s, _)| *s) {
+                    improved = Some((moved_score, moved));
+                }
+            }
+        }
+        match improved {
+            Some((s, moved)) => {
+                current = s;
+                order = moved;
+            }
+            None => {
+                let mut positions = vec![0.; order.len()];
+                order.iter().enumerate().for_each(|(p, &i)| positions[i] = p as f64);
+                return Solution { firsts: vec![order[0]], orders: vec![order], total: 1, exact: false, positions, score: current };
+            }
+        }
+    }
+}
+
+/// Elects the top candidate of the optimal rankings, a tie if they disagree.
+impl<T: Eq + Hash> ElectionRule<T> for Kemeny {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        Decision::new(Outcome::from_winners(self.count(vote).winners), None)
+    }
+}
+
+/// The optimal ranking, or when there are several, candidates ranked by
+/// their average position in them.
+impl<T: Eq + Hash> SocialWelfareFunction<T> for Kemeny {
+    fn rank<'a>(&self, vote: &'a Vote<T>) -> PreOrder<&'a T> {
+        let positions = self.solve(vote).positions;
+        let scores: Vec<(&T, f64)> = vote.candidates().iter().zip(positions).map(|(c, p)| (c, -p)).collect();
+        PreOrder::from_scores(&scores)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::tennessee;
+    use crate::{ElectionRule, Kemeny, KemenyMode, Outcome, PreOrder, SocialWelfareFunction, Vote};
+
+    #[test]
+    fn kemeny_ranking() {
+        let vote = tennessee();
+        let expected = PreOrder::from(vec![&"Nashville", &"Chattanooga", &"Knoxville", &"Memphis"]);
+        let exact = Kemeny { mode: KemenyMode::Exact }.count(&vote);
+        assert_eq!(exact.rankings, vec![expected.clone()]);
+        assert_eq!(exact.score, 393);
+        let heuristic = Kemeny { mode: KemenyMode::LocalSearch }.count(&vote);
+        assert_eq!(heuristic.rankings, vec![expected]);
+        assert_eq!(heuristic.score, 393);
+    }
+
+    #[test]
+    fn every_optimal_ranking() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(1, vec!["a", "b", "c"])
+            .ballot(1, vec!["b", "c", "a"])
+            .ballot(1, vec!["c", "a", "b"])
+            .build()
+            .unwrap();
+        let rule = Kemeny { mode: KemenyMode::Exact };
+        let count = rule.count(&vote);
+        assert_eq!(count.score, 5);
+        assert_eq!(
+            count.rankings,
+            vec![
+                PreOrder::from(vec![&"a", &"b", &"c"]),
+                PreOrder::from(vec![&"b", &"c", &"a"]),
+                PreOrder::from(vec![&"c", &"a", &"b"]),
+            ]
+        );
+        assert_eq!(rule.elect(&vote).outcome, Outcome::Tie(vec![&"a", &"b", &"c"]));
+        assert_eq!(rule.rank(&vote), PreOrder::weak(vec![vec![&"a", &"b", &"c"]]));
+    }
+
+    #[test]
+    fn many_optimal_rankings() {
+        let candidates: Vec<usize> = (0..9).collect();
+        let vote = Vote::builder().candidates(candidates.clone()).build().unwrap();
+        let rule = Kemeny { mode: KemenyMode::Exact };
+        let count = rule.count(&vote);
+        assert_eq!(count.total, 362_880);
+        assert!(count.exact);
+        assert_eq!(count.rankings.len(), Kemeny::MAX_RANKINGS);
+        assert_eq!(count.rankings[1], PreOrder::from(vec![&0, &1, &2, &3, &4, &5, &6, &8, &7]));
+        assert_eq!(count.winners.len(), 9);
+        assert_eq!(rule.rank(&vote), PreOrder::weak(vec![candidates.iter().collect()]));
+
+        // Too many candidates to search exactly.
+        let vote = Vote::builder()
+            .candidates(0..64)
+            .ballot(1, (0..64).rev().collect::<Vec<_>>())
+            .build()
+            .unwrap();
+        let count = rule.count(&vote);
+        assert!(!count.exact);
+        assert_eq!((count.total, count.winners), (1, vec![&63]));
+    }
+}
@@ -1,115 +1,487 @@
-use std::collections::{HashSet, HashMap};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Debug;
 use std::hash::Hash;
 use std::ops::Deref;
-use itertools::Itertools;
+use ndarray::Array2;
+
+pub use bucklin::{Bucklin, BucklinCount};
+pub use completion::{Benham, CondorcetCompletion, TidemanAlternative, Woodall};
+pub use coombs::{Coombs, CoombsCount, CoombsRound};
+pub use copeland::{Copeland, CopelandCount, Record};
+pub use dodgson::{Dodgson, DodgsonCount, DodgsonMode};
+pub use elimination::{Baldwin, EliminationCount, Nanson};
+pub use irv::{InstantRunoff, Round, RunoffCount};
+pub use kemeny::{Kemeny, KemenyCount, KemenyMode};
+pub use minimax::{Minimax, MinimaxCount, MinimaxMeasure};
+pub use positional::{PositionalScoring, ScoreVector, ScoringError};
+pub use ranked_pairs::{Lock, Majority, RankedPairs, RankedPairsCount};
+pub use river::{River, RiverCount};
+pub use rule::{CondorcetWinner, Decision, ElectionRule, Outcome, Plurality, SocialWelfareFunction};
+pub use schulze::{Schulze, SchulzeCount, Strength};
+pub use split_cycle::{SplitCycle, SplitCycleCount};
+pub use stable_voting::{StableVoting, StableVotingCount, StableWin};
+pub use stv::{Quota, Stage, Stv, StvCount, StvMethod, Transfer};
+pub use tie::{Resolution, Stake, TieBreak, TieBreaker, TieBroken, TieCallback};
+pub use tournament::{Covering, TournamentSolution};
+pub use two_round::{TwoRound, TwoRoundCount};
+pub use young::{Young, YoungCount, YoungMode};
+
+mod bucklin;
+mod completion;
+mod coombs;
+mod copeland;
+mod dodgson;
+mod elimination;
+mod irv;
+mod kemeny;
+mod majority;
+mod minimax;
+mod positional;
+mod ranked_pairs;
+mod river;
+mod rule;
+mod schulze;
+mod split_cycle;
+mod stable_voting;
+mod stv;
+mod tie;
+mod tournament;
+mod two_round;
+mod young;
 
 pub trait Condorcet<T> {
     fn condorcet_winner(&self) -> Option<&T>;
 }
 
+/// Plurality: every voter gives one point to their first choice.
 pub trait OneStage<T> {
-    fn one_stage(&self) -> Option<&T>;
+    fn one_stage(&self) -> Tally<'_, T>;
+}
+
+/// The result of a single round of counting.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tally<'a, T> {
+    /// Every candidate with its score, highest first. Equal scores keep the
+    /// candidates' declaration order.
+    pub scores: Vec<(&'a T, f64)>,
+    /// The candidates sharing the highest score. Empty when no ballot ranks
+    /// anyone, unless a single candidate is running.
+    pub winners: Vec<&'a T>,
+}
+
+impl<'a, T> Tally<'a, T> {
+    /// Builds a tally from scores listed in declaration order, `counted`
+    /// telling whether any ballot was counted. Scores may be zero or negative.
+    fn new(mut scores: Vec<(&'a T, f64)>, counted: bool) -> Self {
+        scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or_else(|| b.1.total_cmp(&a.1)));
+        let winners = match scores.first() {
+            Some(&(_, best)) if counted || scores.len() == 1 => scores.iter()
+                .take_while(|(_, score)| same_score(*score, best))
+                .map(|&(candidate, _)| candidate)
+                .collect(),
+            _ => vec![],
+        };
+        Tally { scores, winners }
+    }
+
+    /// The winner, if there is exactly one.
+    pub fn winner(&self) -> Option<&'a T> {
+        match self.winners.as_slice() {
+            [winner] => Some(winner),
+            _ => None,
+        }
+    }
+
+    pub fn score_of(&self, candidate: &T) -> Option<f64>
+        where T: Eq
+    {
+        self.scores.iter()
+            .find(|(c, _)| *c == candidate)
+            .map(|&(_, score)| score)
+    }
+}
+
+/// Scores are sums of fractional credits, so compare them with some leeway.
+fn same_score(a: f64, b: f64) -> bool {
+    (a - b).abs() <= 1e-9 * a.abs().max(b.abs()).max(1.)
 }
 
+/// A voter's ranking of the candidates, as a weak order: a list of
+/// indifference classes, most preferred first. `A > B = C > D` is
+/// `[[A], [B, C], [D]]`.
+///
+/// A ranking may be truncated: candidates it leaves out are considered ranked
+/// below every candidate it mentions, and the voter expresses no preference
+/// between two candidates it both leaves out.
+///
+/// Rules count ties the following way: pairwise comparisons abstain on two
+/// candidates of the same class, and positional scores share the points of
+/// the positions a class spans evenly between its members (a two-way tie for
+/// first place gives half a plurality vote to each).
 #[derive(Clone, Debug, Eq, PartialOrd, PartialEq)]
-struct PreOrder<T>(Vec<T>);
+pub struct PreOrder<T>(Vec<Vec<T>>);
+
+/// A strict ranking, without ties.
+impl<T> From<Vec<T>> for PreOrder<T> {
+    fn from(order: Vec<T>) -> Self {
+        PreOrder(order.into_iter().map(|c| vec![c]).collect())
+    }
+}
 
 impl<T: Eq> Deref for PreOrder<T> {
-    type Target = Vec<T>;
+    type Target = Vec<Vec<T>>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
-impl<T: Eq> PreOrder<T> {
-    pub fn who_is_first<'a>(&self, a: &'a T, b: &'a T) -> Option<&'a T> {
-        for it in &self.0 {
-            if a == it {
-                return Some(a);
+/// How a ballot places a candidate relative to another one.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Preference {
+    Above,
+    Below,
+    /// Both candidates are in the same indifference class.
+    Indifferent,
+    /// Neither candidate is ranked: the ballot abstains on this pair.
+    Incomparable,
+}
+
+impl<T> PreOrder<T> {
+    /// Builds a ranking from its indifference classes, most preferred first.
+    /// Empty classes are dropped.
+    pub fn weak(classes: Vec<Vec<T>>) -> Self {
+        PreOrder(classes.into_iter().filter(|class| !class.is_empty()).collect())
+    }
+
+    /// The most preferred candidates, if the ballot ranks anyone.
+    pub fn top(&self) -> Option<&[T]> {
+        self.0.first().map(Vec::as_slice)
+    }
+
+    /// Every ranked candidate, from the most to the least preferred.
+    pub fn ranked(&self) -> impl Iterator<Item = &T> {
+        self.0.iter().flatten()
+    }
+
+    /// The same ranking over other values, e.g. candidate names from ids.
+    pub fn map<U, F: FnMut(T) -> U>(self, mut f: F) -> PreOrder<U> {
+        PreOrder(self.0.into_iter().map(|class| class.into_iter().map(&mut f).collect()).collect())
+    }
+}
+
+impl<'a, T> PreOrder<&'a T> {
+    /// Ranks candidates by decreasing score, equal scores being tied.
+    pub(crate) fn from_scores(scores: &[(&'a T, f64)]) -> Self {
+        let mut sorted = scores.to_vec();
+        sorted.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or_else(|| b.1.total_cmp(&a.1)));
+        let mut classes: Vec<Vec<&T>> = vec![];
+        let mut last = None;
+        for (candidate, score) in sorted {
+            match (classes.last_mut(), last) {
+                (Some(class), Some(last)) if same_score(score, last) => class.push(candidate),
+                _ => classes.push(vec![candidate]),
             }
-            if b == it {
-                return Some(b);
+            last = Some(score);
+        }
+        PreOrder(classes)
+    }
+}
+
+impl<T: Eq> PreOrder<T> {
+    /// Index of the indifference class holding `candidate`, `None` if the
+    /// ballot does not rank it.
+    pub fn rank_of(&self, candidate: &T) -> Option<usize> {
+        self.0.iter().position(|class| class.contains(candidate))
+    }
+
+    /// Where `a` stands relative to `b` on this ballot.
+    pub fn who_is_first(&self, a: &T, b: &T) -> Preference {
+        for class in &self.0 {
+            match (class.contains(a), class.contains(b)) {
+                (true, true) => return Preference::Indifferent,
+                (true, false) => return Preference::Above,
+                (false, true) => return Preference::Below,
+                (false, false) => {}
             }
         }
-        None
+        Preference::Incomparable
     }
 }
 
-type Ballot<T> = (usize, PreOrder<T>);
+/// A group of voters casting the same ranking: `(number of voters, ranking)`.
+pub type Ballot<T> = (usize, PreOrder<T>);
+
+/// A candidate, interned: its index in the election's declaration order.
+///
+/// Ballots are stored as rankings of ids, so counting compares and indexes
+/// small integers rather than hashing candidates.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct CandidateId(u32);
+
+impl CandidateId {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
 
+/// An election: the candidates running and the ballots cast.
+///
+/// Built through [`Vote::builder`], which guarantees every ballot only ranks
+/// known candidates, and each of them at most once.
 #[derive(Debug, Clone, Eq, PartialEq)]
-struct Vote<T: Eq + Hash> {
-    candidates: HashSet<T>,
+pub struct Vote<T: Eq + Hash> {
+    candidates: Vec<T>,
+    ids: HashMap<T, CandidateId>,
+    ballots: Vec<Ballot<CandidateId>>,
+}
+
+impl<T: Eq + Hash> Vote<T> {
+    pub fn builder() -> VoteBuilder<T> {
+        VoteBuilder {
+            candidates: vec![],
+            ballots: vec![],
+        }
+    }
+
+    /// The candidates, in the order they were declared: the `i`-th one has
+    /// id `i`.
+    pub fn candidates(&self) -> &[T] {
+        &self.candidates
+    }
+
+    /// Every candidate id, in declaration order.
+    pub fn ids(&self) -> impl Iterator<Item = CandidateId> + Clone {
+        (0..self.candidates.len() as u32).map(CandidateId)
+    }
+
+    pub fn candidate(&self, id: CandidateId) -> &T {
+        &self.candidates[id.index()]
+    }
+
+    pub fn id_of(&self, candidate: &T) -> Option<CandidateId> {
+        self.ids.get(candidate).cloned()
+    }
+
+    /// The ballots, ranking candidate ids.
+    pub fn ballots(&self) -> &[Ballot<CandidateId>] {
+        &self.ballots
+    }
+
+    /// Whether some voter ranks at least one candidate.
+    pub(crate) fn has_preferences(&self) -> bool {
+        self.ballots.iter().any(|(voters, ballot)| *voters > 0 && !ballot.is_empty())
+    }
+}
+
+/// Why a [`VoteBuilder`] refused to build an election.
+///
+/// `ballot` fields are the index of the offending ballot, in the order the
+/// ballots were added.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum VoteError<T> {
+    NoCandidates,
+    DuplicateCandidate(T),
+    /// More candidates than a [`CandidateId`] can number.
+    TooManyCandidates,
+    UnknownCandidate { ballot: usize, candidate: T },
+    DuplicateInBallot { ballot: usize, candidate: T },
+}
+
+impl<T: Debug> fmt::Display for VoteError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VoteError::NoCandidates => write!(f, "the election has no candidates"),
+            VoteError::DuplicateCandidate(c) => write!(f, "candidate {:?} is declared twice", c),
+            VoteError::TooManyCandidates => write!(f, "the election has more than {} candidates", u32::MAX),
+            VoteError::UnknownCandidate { ballot, candidate } => {
+                write!(f, "ballot {} ranks unknown candidate {:?}", ballot, candidate)
+            }
+            VoteError::DuplicateInBallot { ballot, candidate } => {
+                write!(f, "ballot {} ranks candidate {:?} more than once", ballot, candidate)
+            }
+        }
+    }
+}
+
+impl<T: Debug> Error for VoteError<T> {}
+
+#[derive(Debug, Clone)]
+pub struct VoteBuilder<T> {
+    candidates: Vec<T>,
     ballots: Vec<Ballot<T>>,
 }
 
-impl<T: Eq + Hash + Clone> Condorcet<T> for Vote<T> {
-    fn condorcet_winner(&self) -> Option<&T> {
-        let mut res = None;
-        'outer: for candidate in &self.candidates {
-            let mut s: HashSet<T> = HashSet::new();
-            s.insert(candidate.to_owned());
-            for other_candidate in self.candidates.difference(&s) {
-                let mut scores = (0, 0);
-                for (voters, ballot) in &self.ballots {
-                    if ballot.who_is_first(candidate, other_candidate).unwrap() == candidate {
-                        scores.0 += *voters
-                    } else {
-                        scores.1 += *voters
+impl<T: Eq + Hash + Clone> VoteBuilder<T> {
+    pub fn candidate(mut self, candidate: T) -> Self {
+        self.candidates.push(candidate);
+        self
+    }
+
+    pub fn candidates<I: IntoIterator<Item = T>>(mut self, candidates: I) -> Self {
+        self.candidates.extend(candidates);
+        self
+    }
+
+    /// Adds `count` voters ranking the candidates as `order`.
+    pub fn ballot<O: Into<PreOrder<T>>>(mut self, count: usize, order: O) -> Self {
+        self.ballots.push((count, order.into()));
+        self
+    }
+
+    pub fn build(self) -> Result<Vote<T>, VoteError<T>> {
+        if self.candidates.is_empty() {
+            return Err(VoteError::NoCandidates);
+        }
+        if self.candidates.len() > u32::MAX as usize {
+            return Err(VoteError::TooManyCandidates);
+        }
+        let mut ids = HashMap::with_capacity(self.candidates.len());
+        for (i, candidate) in self.candidates.iter().enumerate() {
+            if ids.insert(candidate.clone(), CandidateId(i as u32)).is_some() {
+                return Err(VoteError::DuplicateCandidate(candidate.clone()));
+            }
+        }
+        let mut ballots = Vec::with_capacity(self.ballots.len());
+        for (i, (count, order)) in self.ballots.into_iter().enumerate() {
+            let mut seen = vec![false; self.candidates.len()];
+            let mut classes = Vec::with_capacity(order.len());
+            for class in order.0 {
+                let mut id_class = Vec::with_capacity(class.len());
+                for candidate in class {
+                    let id = match ids.get(&candidate) {
+                        Some(&id) => id,
+                        None => return Err(VoteError::UnknownCandidate { ballot: i, candidate }),
+                    };
+                    if seen[id.index()] {
+                        return Err(VoteError::DuplicateInBallot { ballot: i, candidate });
                     }
+                    seen[id.index()] = true;
+                    id_class.push(id);
                 }
-                if scores.0 <= scores.1 {
-                    continue 'outer;
+                classes.push(id_class);
+            }
+            ballots.push((count, PreOrder(classes)));
+        }
+        Ok(Vote {
+            candidates: self.candidates,
+            ids,
+            ballots,
+        })
+    }
+}
+
+impl<T: Eq + Hash> Vote<T> {
+    /// Head-to-head results: entry `[i, j]` is the number of voters ranking
+    /// the `i`-th candidate above the `j`-th one, candidates being indexed in
+    /// declaration order.
+    pub fn pairwise_matrix(&self) -> Array2<usize> {
+        let n = self.candidates.len();
+        let mut matrix = Array2::zeros((n, n));
+        let mut ranks = vec![None; n];
+        for (voters, ballot) in &self.ballots {
+            ranks.iter_mut().for_each(|rank| *rank = None);
+            for (rank, class) in ballot.iter().enumerate() {
+                for candidate in class {
+                    ranks[candidate.index()] = Some(rank);
+                }
+            }
+            for (i, rank_i) in ranks.iter().enumerate() {
+                let rank_i = match rank_i {
+                    Some(rank_i) => rank_i,
+                    None => continue,
+                };
+                for (j, rank_j) in ranks.iter().enumerate() {
+                    // Unranked candidates come after every ranked one.
+                    if rank_j.is_none_or(|rank_j| *rank_i < rank_j) {
+                        matrix[[i, j]] += voters;
+                    }
                 }
             }
-            res = Some(candidate);
         }
-        res
+        matrix
+    }
+}
+
+/// The candidate of `among` beating every other one of them head to head in
+/// `matrix`, ignoring everybody else.
+fn condorcet_winner_among(matrix: &Array2<usize>, among: &[CandidateId]) -> Option<CandidateId> {
+    among.iter().cloned().find(|&i| {
+        among.iter().all(|&j| i == j || matrix[[i.index(), j.index()]] > matrix[[j.index(), i.index()]])
+    })
+}
+
+impl<T: Eq + Hash + Clone> Condorcet<T> for Vote<T> {
+    fn condorcet_winner(&self) -> Option<&T> {
+        let all: Vec<CandidateId> = self.ids().collect();
+        condorcet_winner_among(&self.pairwise_matrix(), &all).map(|id| self.candidate(id))
     }
 }
 
 impl<T: Eq + Hash + Clone> OneStage<T> for Vote<T> {
-    fn one_stage(&self) -> Option<&T> {
-        let mut scores: HashMap<&T, usize> = HashMap::new();
-        for (n, candidate) in self.ballots.iter()
-            .map(|(n, ballot)| (n, &ballot[0]))
+    fn one_stage(&self) -> Tally<'_, T> {
+        let mut scores = vec![0.; self.candidates.len()];
+        for (n, top) in self.ballots.iter()
+            .filter_map(|(n, ballot)| ballot.top().map(|top| (n, top)))
         {
-            scores.entry(candidate)
-                .and_modify(move |x| *x += *n)
-                .or_insert(0);
-        }
-        let mut winner = None;
-        for (candidate, score) in scores.iter().sorted_by_key(|s| s.1) {
-            if let Some((candidate_old, score_before)) = winner {
-                return if score_before == score {
-                    None
-                } else {
-                    Some(candidate_old)
-                }
+            let credit = *n as f64 / top.len() as f64;
+            for candidate in top {
+                scores[candidate.index()] += credit;
             }
-            winner = Some((candidate, score));
         }
-        unreachable!()
+        Tally::new(self.candidates.iter().zip(scores).collect(), self.has_preferences())
+    }
+}
+
+/// Elections shared by the tests of several rules.
+#[cfg(test)]
+mod fixtures {
+    use crate::Vote;
+
+    /// Tennessee choosing its capital, every city's voters ranking the
+    /// cities from the nearest.
+    pub(crate) fn tennessee() -> Vote<&'static str> {
+        Vote::builder()
+            .candidates(vec!["Memphis", "Nashville", "Chattanooga", "Knoxville"])
+            .ballot(42, vec!["Memphis", "Nashville", "Chattanooga", "Knoxville"])
+            .ballot(26, vec!["Nashville", "Chattanooga", "Knoxville", "Memphis"])
+            .ballot(15, vec!["Chattanooga", "Knoxville", "Nashville", "Memphis"])
+            .ballot(17, vec!["Knoxville", "Chattanooga", "Nashville", "Memphis"])
+            .build()
+            .unwrap()
+    }
+
+    /// A majority cycle with truncated ballots: a > c 8:6, b > a 9:8 and
+    /// c > b 12:5.
+    pub(crate) fn cycle() -> Vote<&'static str> {
+        Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(4, vec!["c", "b", "a"])
+            .ballot(8, vec!["a", "c", "b"])
+            .ballot(3, vec!["b"])
+            .ballot(2, vec!["b", "c"])
+            .build()
+            .unwrap()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Condorcet, PreOrder, Vote};
-    use sugars::*;
+    use crate::{Condorcet, OneStage, PreOrder, Preference, Vote, VoteError};
+    use ndarray::array;
 
     #[test]
     fn condorcet_1() {
         assert_eq!(
-            Vote {
-                candidates: hset!("a", "b", "c", ),
-                ballots: vec![
-                    (35, PreOrder(vec!["a", "b", "c"])),
-                    (25, PreOrder(vec!["b", "c", "a"])),
-                    (15, PreOrder(vec!["c", "b", "a"])),
-                ],
-            }
+            Vote::builder()
+                .candidates(vec!["a", "b", "c"])
+                .ballot(35, vec!["a", "b", "c"])
+                .ballot(25, vec!["b", "c", "a"])
+                .ballot(15, vec!["c", "b", "a"])
+                .build()
+                .unwrap()
                 .condorcet_winner()
                 .unwrap(),
             &"b"
@@ -119,20 +491,175 @@ mod tests {
     #[test]
     fn condorcet_2() {
         assert_eq!(
-            Vote {
-                candidates: hset!("a", "b", "c", "d"),
-                ballots: vec![
-                    (42, PreOrder(vec!["a", "b", "c", "d"])),
-                    (26, PreOrder(vec!["b", "c", "d", "a"])),
-                    (17, PreOrder(vec!["d", "c", "b", "a"])),
-                    (15, PreOrder(vec!["c", "d", "b", "a"])),
-                ],
-            }
+            Vote::builder()
+                .candidates(vec!["a", "b", "c", "d"])
+                .ballot(42, vec!["a", "b", "c", "d"])
+                .ballot(26, vec!["b", "c", "d", "a"])
+                .ballot(17, vec!["d", "c", "b", "a"])
+                .ballot(15, vec!["c", "d", "b", "a"])
+                .build()
+                .unwrap()
                 .condorcet_winner()
                 .unwrap(),
             &"b"
         )
     }
-}
 
+    #[test]
+    fn builder_rejects_invalid_elections() {
+        assert_eq!(Vote::<&str>::builder().build(), Err(VoteError::NoCandidates));
+        assert_eq!(
+            Vote::builder().candidates(vec!["a", "b", "a"]).build(),
+            Err(VoteError::DuplicateCandidate("a"))
+        );
+        assert_eq!(
+            Vote::builder()
+                .candidates(vec!["a", "b"])
+                .ballot(3, vec!["a", "b"])
+                .ballot(2, vec!["b", "z"])
+                .build(),
+            Err(VoteError::UnknownCandidate { ballot: 1, candidate: "z" })
+        );
+        assert_eq!(
+            Vote::builder()
+                .candidates(vec!["a", "b"])
+                .ballot(3, vec!["a", "b", "a"])
+                .build(),
+            Err(VoteError::DuplicateInBallot { ballot: 0, candidate: "a" })
+        );
+    }
+
+    #[test]
+    fn truncated_ballots() {
+        let ballot = PreOrder::from(vec!["a", "b"]);
+        assert_eq!(ballot.who_is_first(&"a", &"b"), Preference::Above);
+        assert_eq!(ballot.who_is_first(&"c", &"b"), Preference::Below);
+        assert_eq!(ballot.who_is_first(&"c", &"d"), Preference::Incomparable);
+
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(4, vec!["a"])
+            .ballot(3, vec!["b", "c"])
+            .ballot(2, vec!["c"])
+            .ballot(1, vec![])
+            .build()
+            .unwrap();
+        // a > b 4:3, a > c 4:5 loses, so nobody beats everyone.
+        assert_eq!(vote.condorcet_winner(), None);
+        // The empty ballot abstains rather than panicking.
+        vote.one_stage();
+
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(4, vec!["a"])
+            .ballot(3, vec!["b", "a"])
+            .ballot(2, vec!["c"])
+            .build()
+            .unwrap();
+        assert_eq!(vote.condorcet_winner(), Some(&"a"));
+    }
+
+    #[test]
+    fn weak_orders() {
+        let ballot = PreOrder::weak(vec![vec!["a"], vec!["b", "c"], vec![], vec!["d"]]);
+        assert_eq!(ballot.len(), 3);
+        assert_eq!(ballot.rank_of(&"c"), Some(1));
+        assert_eq!(ballot.who_is_first(&"b", &"c"), Preference::Indifferent);
+        assert_eq!(ballot.who_is_first(&"d", &"b"), Preference::Below);
+        assert_eq!(ballot.who_is_first(&"e", &"d"), Preference::Below);
+
+        assert_eq!(
+            Vote::builder()
+                .candidates(vec!["a", "b"])
+                .ballot(1, PreOrder::weak(vec![vec!["a", "b"], vec!["a"]]))
+                .build(),
+            Err(VoteError::DuplicateInBallot { ballot: 0, candidate: "a" })
+        );
+
+        // b and c tie on the big group, which abstains on their contest.
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(10, PreOrder::weak(vec![vec!["b", "c"], vec!["a"]]))
+            .ballot(3, vec!["b", "a", "c"])
+            .ballot(2, vec!["c", "a", "b"])
+            .build()
+            .unwrap();
+        assert_eq!(vote.condorcet_winner(), Some(&"b"));
+    }
+
+    #[test]
+    fn plurality() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c", "d"])
+            .ballot(4, vec!["a", "b"])
+            .ballot(3, vec!["b", "a"])
+            .ballot(2, PreOrder::weak(vec![vec!["b", "c"]]))
+            .build()
+            .unwrap();
+        let tally = vote.one_stage();
+        assert_eq!(tally.scores, vec![(&"a", 4.), (&"b", 4.), (&"c", 1.), (&"d", 0.)]);
+        assert_eq!(tally.winners, vec![&"a", &"b"]);
+        assert_eq!(tally.winner(), None);
+
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(1, vec!["c"])
+            .ballot(2, vec!["b"])
+            .build()
+            .unwrap();
+        assert_eq!(vote.one_stage().winner(), Some(&"b"));
+    }
+
+    #[test]
+    fn plurality_degenerate_elections() {
+        let vote = Vote::builder().candidate("a").build().unwrap();
+        assert_eq!(vote.one_stage().winner(), Some(&"a"));
+
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b"])
+            .ballot(3, vec![])
+            .build()
+            .unwrap();
+        let tally = vote.one_stage();
+        assert_eq!(tally.scores, vec![(&"a", 0.), (&"b", 0.)]);
+        assert!(tally.winners.is_empty());
+    }
 
+    #[test]
+    fn pairwise_matrix() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(4, vec!["a", "b", "c"])
+            .ballot(3, PreOrder::weak(vec![vec!["b", "c"], vec!["a"]]))
+            .ballot(2, vec!["c"])
+            .build()
+            .unwrap();
+        assert_eq!(
+            vote.pairwise_matrix(),
+            array![
+                [0, 4, 4],
+                [3, 0, 4],
+                [5, 2, 0],
+            ]
+        );
+    }
+
+    #[test]
+    fn candidates_are_interned() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(4, vec!["c", "a"])
+            .build()
+            .unwrap();
+        let ids: Vec<_> = vote.ids().collect();
+        assert_eq!(ids.iter().map(|id| id.index()).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(vote.id_of(&"c"), Some(ids[2]));
+        assert_eq!(vote.id_of(&"z"), None);
+        assert_eq!(vote.candidate(ids[1]), &"b");
+        assert_eq!(vote.ballots()[0], (4, PreOrder::from(vec![ids[2], ids[0]])));
+        assert_eq!(
+            vote.ballots()[0].1.clone().map(|id| *vote.candidate(id)),
+            PreOrder::from(vec!["c", "a"])
+        );
+    }
+}
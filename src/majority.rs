@@ -0,0 +1,148 @@
+use std::hash::Hash;
+
+use crate::{PreOrder, Vote, VoteError};
+
+/// `closure[i][j]` is whether there is a path from `i` to `j` in `graph`.
+pub(crate) fn closure(graph: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    let n = graph.len();
+    let mut reach = graph.to_vec();
+    for k in 0..n {
+        for i in 0..n {
+            if reach[i][k] {
+                let through = reach[k].clone();
+                for (r, t) in reach[i].iter_mut().zip(through) {
+                    *r |= t;
+                }
+            }
+        }
+    }
+    reach
+}
+
+/// The Smith set of the election where only the candidates `among` run.
+pub(crate) fn smith_among(beats: &[Vec<bool>], among: &[usize]) -> Vec<usize> {
+    let beats_or_ties: Vec<Vec<bool>> = among.iter()
+        .map(|&i| among.iter().map(|&j| i != j && !beats[j][i]).collect())
+        .collect();
+    let reach = closure(&beats_or_ties);
+    (0..among.len())
+        .filter(|&a| (0..among.len()).all(|b| a == b || reach[a][b]))
+        .map(|a| among[a])
+        .collect()
+}
+
+/// Queries on the majority relation: who beats whom head to head.
+impl<T: Eq + Hash> Vote<T> {
+    /// `beats[i][j]` is whether the `i`-th candidate beats the `j`-th head to
+    /// head, candidates being indexed by id.
+    pub(crate) fn majority_graph(&self) -> Vec<Vec<bool>> {
+        let matrix = self.pairwise_matrix();
+        let n = self.candidates().len();
+        (0..n).map(|i| (0..n).map(|j| matrix[[i, j]] > matrix[[j, i]]).collect()).collect()
+    }
+
+    /// The smallest set of candidates beating every candidate outside of it:
+    /// the top strongly connected component of the relation "beats or ties".
+    pub fn smith_set(&self) -> Vec<&T> {
+        let all: Vec<usize> = (0..self.candidates().len()).collect();
+        smith_among(&self.majority_graph(), &all)
+            .into_iter()
+            .map(|i| &self.candidates()[i])
+            .collect()
+    }
+
+    /// The union of the smallest sets of candidates no outsider beats: the
+    /// strongly connected components of the relation "beats" that no other
+    /// component beats. Contained in the Smith set, and smaller when some
+    /// contests are tied.
+    pub fn schwartz_set(&self) -> Vec<&T> {
+        let beats = self.majority_graph();
+        let n = beats.len();
+        let reach = closure(&beats);
+        (0..n)
+            .filter(|&i| (0..n).all(|j| !reach[j][i] || reach[i][j]))
+            .map(|i| &self.candidates()[i])
+            .collect()
+    }
+
+    /// The candidates no other candidate beats head to head.
+    pub fn weak_condorcet_winners(&self) -> Vec<&T> {
+        let beats = self.majority_graph();
+        (0..beats.len())
+            .filter(|&i| beats.iter().all(|row| !row[i]))
+            .map(|i| &self.candidates()[i])
+            .collect()
+    }
+
+    /// The candidate every other candidate beats head to head, if any.
+    pub fn condorcet_loser(&self) -> Option<&T> {
+        let beats = self.majority_graph();
+        (0..beats.len())
+            .find(|&i| (0..beats.len()).all(|j| i == j || beats[j][i]))
+            .map(|i| &self.candidates()[i])
+    }
+}
+
+impl<T: Eq + Hash + Clone> Vote<T> {
+    /// The same election with only `candidates` running, ballots ranking
+    /// them in the same order. Fails if `candidates` is empty, repeats a
+    /// candidate or names one not running.
+    pub fn restricted_to(&self, candidates: &[&T]) -> Result<Vote<T>, VoteError<T>> {
+        let mut builder = Vote::builder().candidates(candidates.iter().map(|&c| c.clone()));
+        for (voters, ballot) in self.ballots() {
+            let classes = ballot.iter()
+                .map(|class| {
+                    class.iter()
+                        .map(|&id| self.candidate(id))
+                        .filter(|c| candidates.contains(c))
+                        .cloned()
+                        .collect()
+                })
+                .collect();
+            builder = builder.ballot(*voters, PreOrder::weak(classes));
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Condorcet, Vote};
+
+    #[test]
+    fn smith_and_schwartz_sets() {
+        // a, b and c form a cycle, and all of them beat d.
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c", "d"])
+            .ballot(4, vec!["a", "b", "c", "d"])
+            .ballot(3, vec!["b", "c", "a", "d"])
+            .ballot(3, vec!["c", "a", "b", "d"])
+            .build()
+            .unwrap();
+        assert_eq!(vote.smith_set(), vec![&"a", &"b", &"c"]);
+        assert_eq!(vote.schwartz_set(), vec![&"a", &"b", &"c"]);
+        assert!(vote.weak_condorcet_winners().is_empty());
+        assert_eq!(vote.condorcet_loser(), Some(&"d"));
+
+        let restricted = vote.restricted_to(&vote.smith_set()).unwrap();
+        assert_eq!(restricted.candidates(), &["a", "b", "c"]);
+        assert_eq!(restricted.pairwise_matrix()[[0, 1]], 7);
+    }
+
+    #[test]
+    fn ties_shrink_the_schwartz_set() {
+        // a ties b and beats c, which beats b.
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(2, vec!["a", "c", "b"])
+            .ballot(1, vec!["c", "b", "a"])
+            .ballot(1, vec!["b"])
+            .build()
+            .unwrap();
+        assert_eq!(vote.condorcet_winner(), None);
+        assert_eq!(vote.weak_condorcet_winners(), vec![&"a"]);
+        assert_eq!(vote.smith_set(), vec![&"a", &"b", &"c"]);
+        assert_eq!(vote.schwartz_set(), vec![&"a"]);
+        assert_eq!(vote.condorcet_loser(), None);
+    }
+}
@@ -0,0 +1,161 @@
+use std::hash::Hash;
+
+use crate::{Decision, ElectionRule, Outcome, PreOrder, SocialWelfareFunction, Vote};
+
+/// How Minimax measures a candidate's result against another one.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum MinimaxMeasure {
+    /// The votes against the candidate in the contests it loses, 0 in the
+    /// ones it wins or ties.
+    WinningVotes,
+    /// The votes against the candidate minus the votes for it.
+    Margins,
+    /// The votes against the candidate, whoever wins the contest. Unlike the
+    /// others, it may miss a Condorcet winner when ballots are truncated, as
+    /// a candidate many ballots leave out faces few votes against it.
+    PairwiseOpposition,
+}
+
+/// Minimax (Simpson–Kramer): elects the candidate whose worst pairwise
+/// result is the least bad.
+///
+/// Under every measure, Minimax may elect a Condorcet loser.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Minimax {
+    pub measure: MinimaxMeasure,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MinimaxCount<'a, T> {
+    /// Every candidate's worst result under the measure, in declaration
+    /// order. The lower, the better.
+    pub worst: Vec<(&'a T, i64)>,
+    pub winners: Vec<&'a T>,
+}
+
+impl Minimax {
+    pub fn count<'a, T: Eq + Hash>(&self, vote: &'a Vote<T>) -> MinimaxCount<'a, T> {
+        let matrix = vote.pairwise_matrix();
+        let worst: Vec<(&T, i64)> = vote.ids()
+            .map(|x| {
+                let worst = vote.ids()
+                    .filter(|&y| y != x)
+                    .map(|y| {
+                        let (against, for_x) = (matrix[[y.index(), x.index()]] as i64, matrix[[x.index(), y.index()]] as i64);
+                        match self.measure {
+                            MinimaxMeasure::WinningVotes if against > for_x => against,
+                            MinimaxMeasure::WinningVotes => 0,
+                            MinimaxMeasure::Margins => against - for_x,
+                            MinimaxMeasure::PairwiseOpposition => against,
+                        }
+                    })
+                    .max()
+                    .unwrap_or(0);
+                (vote.candidate(x), worst)
+            })
+            .collect();
+        let best = worst.iter().map(|&(_, w)| w).min().unwrap();
+        let winners = worst.iter().filter(|&&(_, w)| w == best).map(|&(c, _)| c).collect();
+        MinimaxCount { worst, winners }
+    }
+}
+
+impl<T: Eq + Hash> ElectionRule<T> for Minimax {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        Decision::new(Outcome::from_winners(self.count(vote).winners), None)
+    }
+}
+
+/// Ranks candidates from the least bad worst result up.
+impl<T: Eq + Hash> SocialWelfareFunction<T> for Minimax {
+    fn rank<'a>(&self, vote: &'a Vote<T>) -> PreOrder<&'a T> {
+        let scores: Vec<(&T, f64)> = self.count(vote).worst.into_iter().map(|(c, w)| (c, -w as f64)).collect();
+        PreOrder::from_scores(&scores)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Condorcet, Minimax, MinimaxMeasure, PreOrder, SocialWelfareFunction, Vote};
+
+    const MEASURES: [MinimaxMeasure; 3] = [MinimaxMeasure::WinningVotes, MinimaxMeasure::Margins, MinimaxMeasure::PairwiseOpposition];
+
+    #[test]
+    fn measures_differ_on_truncated_ballots() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c", "d"])
+            .ballot(2, vec!["b", "c"])
+            .ballot(11, vec!["d"])
+            .ballot(6, vec!["a", "d", "b", "c"])
+            .ballot(7, vec!["b", "c"])
+            .ballot(5, vec!["b", "a"])
+            .ballot(2, vec!["a", "c", "b"])
+            .build()
+            .unwrap();
+        let winning_votes = Minimax { measure: MinimaxMeasure::WinningVotes }.count(&vote);
+        assert_eq!(winning_votes.worst, vec![(&"a", 14), (&"b", 17), (&"c", 20), (&"d", 13)]);
+        assert_eq!(winning_votes.winners, vec![&"d"]);
+        let margins = Minimax { measure: MinimaxMeasure::Margins }.count(&vote);
+        assert_eq!(margins.worst, vec![(&"a", 6), (&"b", 1), (&"c", 18), (&"d", 2)]);
+        assert_eq!(margins.winners, vec![&"b"]);
+        let opposition = Minimax { measure: MinimaxMeasure::PairwiseOpposition }.count(&vote);
+        assert_eq!(opposition.winners, vec![&"a"]);
+        assert_eq!(
+            Minimax { measure: MinimaxMeasure::Margins }.rank(&vote),
+            PreOrder::from(vec![&"b", &"d", &"a", &"c"])
+        );
+    }
+
+    #[test]
+    fn condorcet_winner_wins() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(35, vec!["a", "b", "c"])
+            .ballot(25, vec!["b", "c", "a"])
+            .ballot(15, vec!["c", "b", "a"])
+            .build()
+            .unwrap();
+        for &measure in &MEASURES {
+            assert_eq!(Minimax { measure }.count(&vote).winners, vec![vote.condorcet_winner().unwrap()]);
+        }
+    }
+
+    #[test]
+    fn pairwise_opposition_can_miss_the_condorcet_winner() {
+        // a beats b 6 to 5 and c 4 to 3, but c never faces more than 4 votes.
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(2, vec!["c", "a", "b"])
+            .ballot(4, vec!["a"])
+            .ballot(4, vec!["b"])
+            .ballot(1, vec!["c", "b"])
+            .build()
+            .unwrap();
+        assert_eq!(vote.condorcet_winner(), Some(&"a"));
+        assert_eq!(Minimax { measure: MinimaxMeasure::WinningVotes }.count(&vote).winners, vec![&"a"]);
+        assert_eq!(Minimax { measure: MinimaxMeasure::Margins }.count(&vote).winners, vec![&"a"]);
+        let opposition = Minimax { measure: MinimaxMeasure::PairwiseOpposition }.count(&vote);
+        assert_eq!(opposition.worst, vec![(&"a", 5), (&"b", 6), (&"c", 4)]);
+        assert_eq!(opposition.winners, vec![&"c"]);
+    }
+
+    #[test]
+    fn condorcet_loser_can_win() {
+        let vote = Vote::builder()
+            .candidates(vec!["l", "a", "b", "c"])
+            .ballot(2, vec!["l", "a", "b", "c"])
+            .ballot(1, vec!["a", "b", "c", "l"])
+            .ballot(1, vec!["l", "b", "c", "a"])
+            .ballot(2, vec!["b", "c", "a", "l"])
+            .ballot(1, vec!["l", "c", "a", "b"])
+            .ballot(2, vec!["c", "a", "b", "l"])
+            .build()
+            .unwrap();
+        // Every other candidate beats l head to head.
+        let matrix = vote.pairwise_matrix();
+        assert!((1..4).all(|x| matrix[[x, 0]] > matrix[[0, x]]));
+        for &measure in &MEASURES {
+            assert_eq!(Minimax { measure }.count(&vote).winners, vec![&"l"]);
+        }
+    }
+}
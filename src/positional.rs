@@ -0,0 +1,197 @@
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+
+use crate::{Decision, ElectionRule, PreOrder, SocialWelfareFunction, Tally, Vote};
+
+/// Rules giving every candidate points according to its position on each
+/// ballot, the highest total winning.
+///
+/// Tied candidates share the points of the positions they span evenly, and
+/// candidates a ballot leaves out are tied below the ranked ones, except for
+/// the modified Borda count. Empty ballots abstain.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PositionalScoring {
+    /// Points for the first, second, ... position, built by
+    /// [`PositionalScoring::scores`]. Positions past the end get nothing, so
+    /// plurality is `scores(vec![1.])`.
+    Scores(ScoreVector),
+    /// `n - 1` points for the first of `n` candidates, down to 0 for the last.
+    Borda,
+    /// Borda counted on the ranked candidates only: a ballot ranking `k`
+    /// candidates gives `k` points to the first down to 1 to the last, and
+    /// nothing to the candidates it leaves out.
+    ModifiedBorda,
+    /// Dowdall (Nauru): `1 / p` points for the `p`-th position.
+    Dowdall,
+    /// Veto: a point for every candidate but the last.
+    AntiPlurality,
+    /// A point for each of the first `k` candidates.
+    Approval(usize),
+}
+
+/// Points by position, finite but possibly negative.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScoreVector(Vec<f64>);
+
+impl ScoreVector {
+    pub fn points(&self) -> &[f64] {
+        &self.0
+    }
+}
+
+/// Why [`PositionalScoring::scores`] refused a score vector.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ScoringError {
+    /// The points of the position, counted from 0, are infinite or NaN.
+    NotFinite { position: usize },
+}
+
+impl fmt::Display for ScoringError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScoringError::NotFinite { position } => write!(f, "the points of position {} are not finite", position),
+        }
+    }
+}
+
+impl Error for ScoringError {}
+
+impl PositionalScoring {
+    /// [`PositionalScoring::Scores`], checking every point is finite.
+    pub fn scores(points: Vec<f64>) -> Result<Self, ScoringError> {
+        match points.iter().position(|p| !p.is_finite()) {
+            Some(position) => Err(ScoringError::NotFinite { position }),
+            None => Ok(PositionalScoring::Scores(ScoreVector(points))),
+        }
+    }
+
+    /// Points by position on a ballot ranking `ranked` of `n` candidates.
+    fn points(&self, n: usize, ranked: usize) -> Vec<f64> {
+        let mut points: Vec<f64> = match self {
+            PositionalScoring::Scores(scores) => scores.0.iter().cloned().take(n).collect(),
+            PositionalScoring::Borda => (0..n).rev().map(|p| p as f64).collect(),
+            PositionalScoring::ModifiedBorda => (1..=ranked).rev().map(|p| p as f64).collect(),
+            PositionalScoring::Dowdall => (1..=n).map(|p| 1. / p as f64).collect(),
+            PositionalScoring::AntiPlurality => vec![1.; n.saturating_sub(1)],
+            PositionalScoring::Approval(k) => vec![1.; (*k).min(n)],
+        };
+        points.resize(n, 0.);
+        points
+    }
+
+    pub fn tally<'a, T: Eq + Hash>(&self, vote: &'a Vote<T>) -> Tally<'a, T> {
+        let (scores, _) = self.scores_among(vote, &vec![true; vote.candidates().len()]);
+        Tally::new(vote.candidates().iter().zip(scores).collect(), vote.has_preferences())
+    }
+
+    /// Points of every candidate, indexed by id, counted as if only the
+    /// `continuing` candidates were running, and the voters whose ballots
+    /// rank none of them and abstain.
+    pub(crate) fn scores_among<T: Eq + Hash>(&self, vote: &Vote<T>, continuing: &[bool]) -> (Vec<f64>, f64) {
+        let n = continuing.iter().filter(|&&c| c).count();
+        let mut scores = vec![0.; continuing.len()];
+        let mut abstaining = 0.;
+        let mut ranked = vec![false; continuing.len()];
+        for (voters, ballot) in vote.ballots() {
+            let classes: Vec<Vec<_>> = ballot.iter()
+                .map(|class| class.iter().cloned().filter(|id| continuing[id.index()]).collect::<Vec<_>>())
+                .filter(|class| !class.is_empty())
+                .collect();
+            if classes.is_empty() {
+                abstaining += *voters as f64;
+                continue;
+            }
+            ranked.iter_mut().for_each(|r| *r = false);
+            classes.iter().flatten().for_each(|id| ranked[id.index()] = true);
+            let unranked: Vec<_> = vote.ids().filter(|id| continuing[id.index()] && !ranked[id.index()]).collect();
+            let points = self.points(n, n - unranked.len());
+            let mut position = 0;
+            for class in classes.iter().chain(Some(&unranked).filter(|u| !u.is_empty())) {
+                let span = &points[position..position + class.len()];
+                let share = span.iter().sum::<f64>() / class.len() as f64 * *voters as f64;
+                for id in class {
+                    scores[id.index()] += share;
+                }
+                position += class.len();
+            }
+        }
+        (scores, abstaining)
+    }
+}
+
+impl<T: Eq + Hash> ElectionRule<T> for PositionalScoring {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        self.tally(vote).into()
+    }
+}
+
+impl<T: Eq + Hash> SocialWelfareFunction<T> for PositionalScoring {
+    fn rank<'a>(&self, vote: &'a Vote<T>) -> PreOrder<&'a T> {
+        PreOrder::from_scores(&self.tally(vote).scores)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::tennessee;
+    use crate::{ElectionRule, OneStage, Outcome, PositionalScoring, PreOrder, ScoringError, SocialWelfareFunction, Vote};
+
+    #[test]
+    fn scoring_rules() {
+        let vote = tennessee();
+        let borda = PositionalScoring::Borda.tally(&vote);
+        assert_eq!(borda.score_of(&"Memphis"), Some(126.));
+        assert_eq!(borda.score_of(&"Nashville"), Some(194.));
+        assert_eq!(borda.score_of(&"Chattanooga"), Some(173.));
+        assert_eq!(borda.score_of(&"Knoxville"), Some(107.));
+        assert_eq!(borda.winner(), Some(&"Nashville"));
+
+        assert_eq!(PositionalScoring::scores(vec![1.]).unwrap().tally(&vote), vote.one_stage());
+        assert_eq!(PositionalScoring::AntiPlurality.tally(&vote).winners, vec![&"Nashville", &"Chattanooga"]);
+        assert_eq!(PositionalScoring::Approval(2).tally(&vote).score_of(&"Chattanooga"), Some(58.));
+        assert_eq!(PositionalScoring::Dowdall.tally(&vote).winner(), Some(&"Nashville"));
+        assert_eq!(
+            PositionalScoring::Borda.rank(&vote),
+            PreOrder::from(vec![&"Nashville", &"Chattanooga", &"Memphis", &"Knoxville"])
+        );
+    }
+
+    #[test]
+    fn truncated_and_tied_ballots() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c", "d"])
+            .ballot(2, vec!["a"])
+            .ballot(1, PreOrder::weak(vec![vec!["b", "c"], vec!["d"]]))
+            .ballot(5, vec![])
+            .build()
+            .unwrap();
+        // Unranked candidates share positions 2 to 4: (2 + 1 + 0) / 3 points.
+        let borda = PositionalScoring::Borda.tally(&vote);
+        assert_eq!(borda.scores, vec![(&"a", 6.), (&"b", 4.5), (&"c", 4.5), (&"d", 3.)]);
+        let modified = PositionalScoring::ModifiedBorda.tally(&vote);
+        assert_eq!(modified.scores, vec![(&"b", 2.5), (&"c", 2.5), (&"a", 2.), (&"d", 1.)]);
+    }
+
+    #[test]
+    fn penalties_and_invalid_scores() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(4, vec!["a", "b", "c"])
+            .ballot(3, vec!["b", "c", "a"])
+            .build()
+            .unwrap();
+        // Veto as a penalty: nobody scores above 0, a is vetoed 3 times.
+        let veto = PositionalScoring::scores(vec![0., 0., -1.]).unwrap();
+        assert_eq!(veto.tally(&vote).scores, vec![(&"b", 0.), (&"a", -3.), (&"c", -4.)]);
+        assert_eq!(veto.elect(&vote).outcome, Outcome::Winner(&"b"));
+        assert_eq!(
+            PositionalScoring::scores(vec![1., f64::NAN]),
+            Err(ScoringError::NotFinite { position: 1 })
+        );
+        assert_eq!(
+            PositionalScoring::scores(vec![f64::INFINITY]),
+            Err(ScoringError::NotFinite { position: 0 })
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use std::cmp::Reverse;
+use std::hash::Hash;
+
+use crate::{CandidateId, Decision, ElectionRule, Outcome, PreOrder, SocialWelfareFunction, Stake, Strength, TieBreak, TieBreaker, Vote};
+
+/// Tideman's ranked pairs: pairwise majorities are considered from the
+/// strongest down and locked in, unless they would create a cycle with the
+/// majorities already locked. The winner is unbeaten in the locked majorities.
+///
+/// Majorities of equal strength are ordered after a ranking of the candidates
+/// drawn with `tie_breaker`: the one whose winner ranks higher comes first,
+/// then the one whose loser ranks lower.
+#[derive(Clone, Debug)]
+pub struct RankedPairs<T> {
+    pub strength: Strength,
+    pub tie_breaker: TieBreaker<T>,
+}
+
+/// A pairwise majority.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Majority<'a, T> {
+    pub winner: &'a T,
+    pub loser: &'a T,
+    pub strength: i64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Lock<'a, T> {
+    Locked(Majority<'a, T>),
+    /// Skipped as the locked majorities already lead from its loser to its
+    /// winner along `path`, from the loser to the winner.
+    Skipped { majority: Majority<'a, T>, path: Vec<&'a T> },
+    /// Skipped by [`River`](crate::River) as its loser already lost the
+    /// locked majority of `by`.
+    Defeated { majority: Majority<'a, T>, by: &'a T },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RankedPairsCount<'a, T> {
+    /// Every majority, in the order they were considered.
+    pub log: Vec<Lock<'a, T>>,
+    pub winners: Vec<&'a T>,
+    /// Candidates ranked below every candidate above them in the locked
+    /// majorities.
+    pub ranking: PreOrder<&'a T>,
+    pub tie_breaks: Vec<TieBreak<'a, T>>,
+}
+
+/// A path from `from` to `to` along the edges of `graph`, if there is one.
+pub(crate) fn path(graph: &[Vec<bool>], from: usize, to: usize) -> Option<Vec<usize>> {
+    let mut previous = vec![None; graph.len()];
+    let mut stack = vec![from];
+    let mut seen = vec![false; graph.len()];
+    seen[from] = true;
+    while let Some(node) = stack.pop() {
+        if node == to {
+            let mut path = vec![to];
+            while let Some(p) = previous[*path.last().unwrap()] {
+                path.push(p);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for next in 0..graph.len() {
+            if !graph[node][next] || seen[next] {
+                continue;
+            }
+            seen[next] = true;
+            previous[next] = Some(node);
+            stack.push(next);
+        }
+    }
+    None
+}
+
+/// Ranks the nodes of an acyclic `graph` by peeling off those without
+/// incoming edges.
+pub(crate) fn sources_ranking(graph: &[Vec<bool>]) -> Vec<Vec<usize>> {
+    let mut remaining: Vec<usize> = (0..graph.len()).collect();
+    let mut classes = vec![];
+    while !remaining.is_empty() {
+        let sources: Vec<usize> = remaining.iter()
+            .cloned()
+            .filter(|&i| remaining.iter().all(|&j| !graph[j][i]))
+            .collect();
+        remaining.retain(|i| !sources.contains(i));
+        classes.push(sources);
+    }
+    classes
+}
+
+/// A majority between candidates indexed by id: winner, loser and strength.
+pub(crate) type Edge = (usize, usize, i64);
+
+/// Every majority, strongest first, majorities of equal strength ordered
+/// after a ranking of the candidates drawn with `tie_breaker`, and the ties
+/// broken to draw it.
+pub(crate) fn ordered_majorities<'a, T: Eq + Hash>(
+    vote: &'a Vote<T>,
+    strength: Strength,
+    tie_breaker: &TieBreaker<T>,
+) -> (Vec<Edge>, Vec<TieBreak<'a, T>>) {
+    let matrix = vote.pairwise_matrix();
+    let n = vote.candidates().len();
+    let mut majorities: Vec<Edge> = (0..n)
+        .flat_map(|i| (0..n).map(move |j| (i, j)))
+        .map(|(i, j)| (i, j, strength.of(&matrix, i, j)))
+        .filter(|&(_, _, strength)| strength > 0)
+        .collect();
+    majorities.sort_by_key(|&(_, _, strength)| Reverse(strength));
+
+    let mut tie_breaks = vec![];
+    if majorities.windows(2).any(|w| w[0].2 == w[1].2) {
+        let mut order = vec![];
+        let mut remaining: Vec<&T> = vote.candidates().iter().collect();
+        while remaining.len() > 1 {
+            let tie_break = tie_breaker.break_tie(&remaining, Stake::Win, &[]);
+            remaining.retain(|c| *c != tie_break.chosen);
+            order.push(vote.id_of(tie_break.chosen).unwrap().index());
+            tie_breaks.push(tie_break);
+        }
+        order.extend(remaining.iter().map(|c| vote.id_of(c).unwrap().index()));
+        let place = |i: usize| order.iter().position(|&o| o == i).unwrap();
+        majorities.sort_by(|a, b| {
+            b.2.cmp(&a.2)
+                .then(place(a.0).cmp(&place(b.0)))
+                .then(place(b.1).cmp(&place(a.1)))
+        });
+    }
+    (majorities, tie_breaks)
+}
+
+impl<T: Eq + Hash> RankedPairs<T> {
+    pub fn count<'a>(&self, vote: &'a Vote<T>) -> RankedPairsCount<'a, T> {
+        let n = vote.candidates().len();
+        let (majorities, tie_breaks) = ordered_majorities(vote, self.strength, &self.tie_breaker);
+
+        let name = |i: usize| vote.candidate(CandidateId(i as u32));
+        let mut locked = vec![vec![false; n]; n];
+        let mut log = vec![];
+        for (winner, loser, strength) in majorities {
+            let majority = Majority { winner: name(winner), loser: name(loser), strength };
+            match path(&locked, loser, winner) {
+                Some(path) => log.push(Lock::Skipped { majority, path: path.into_iter().map(name).collect() }),
+                None => {
+                    locked[winner][loser] = true;
+                    log.push(Lock::Locked(majority));
+                }
+            }
+        }
+        let ranking = PreOrder::weak(sources_ranking(&locked)).map(name);
+        RankedPairsCount {
+            log,
+            winners: ranking.top().map_or(vec![], <[_]>::to_vec),
+            ranking,
+            tie_breaks,
+        }
+    }
+}
+
+impl<T: Eq + Hash> ElectionRule<T> for RankedPairs<T> {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        let count = self.count(vote);
+        Decision {
+            outcome: Outcome::from_winners(count.winners),
+            scores: None,
+            tie_breaks: count.tie_breaks,
+        }
+    }
+}
+
+impl<T: Eq + Hash> SocialWelfareFunction<T> for RankedPairs<T> {
+    fn rank<'a>(&self, vote: &'a Vote<T>) -> PreOrder<&'a T> {
+        self.count(vote).ranking
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::cycle;
+    use crate::{Lock, Majority, PreOrder, RankedPairs, Strength, TieBreaker, Vote};
+
+    #[test]
+    fn cycles_are_skipped() {
+        let vote = cycle();
+        let count = RankedPairs { strength: Strength::WinningVotes, tie_breaker: TieBreaker::Lot(0) }.count(&vote);
+        assert_eq!(
+            count.log,
+            vec![
+                Lock::Locked(Majority { winner: &"c", loser: &"b", strength: 12 }),
+                Lock::Locked(Majority { winner: &"b", loser: &"a", strength: 9 }),
+                Lock::Skipped {
+                    majority: Majority { winner: &"a", loser: &"c", strength: 8 },
+                    path: vec![&"c", &"b", &"a"],
+                },
+            ]
+        );
+        assert_eq!(count.ranking, PreOrder::from(vec![&"c", &"b", &"a"]));
+        assert!(count.tie_breaks.is_empty());
+
+        let count = RankedPairs { strength: Strength::Margins, tie_breaker: TieBreaker::Lot(0) }.count(&vote);
+        assert_eq!(count.winners, vec![&"a"]);
+    }
+
+    #[test]
+    fn equal_majorities_follow_the_tie_breaking_ranking() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(1, vec!["a", "b", "c"])
+            .ballot(1, vec!["b", "c", "a"])
+            .ballot(1, vec!["c", "a", "b"])
+            .build()
+            .unwrap();
+        let rule = RankedPairs { strength: Strength::WinningVotes, tie_breaker: TieBreaker::Priority(vec!["b", "a", "c"]) };
+        let count = rule.count(&vote);
+        assert_eq!(count.log[0], Lock::Locked(Majority { winner: &"b", loser: &"c", strength: 2 }));
+        assert_eq!(count.ranking, PreOrder::from(vec![&"a", &"b", &"c"]));
+        assert_eq!(count.tie_breaks.len(), 2);
+    }
+}
@@ -0,0 +1,107 @@
+use std::hash::Hash;
+
+use crate::ranked_pairs::{ordered_majorities, path};
+use crate::{CandidateId, Decision, ElectionRule, Lock, Majority, Outcome, Strength, TieBreak, TieBreaker, Vote};
+
+/// River: like ranked pairs, majorities are locked in from the strongest
+/// down unless they would create a cycle, but a majority is also skipped
+/// when its loser already lost a locked majority. The locked majorities form
+/// a tree whose root is the winner.
+///
+/// Majorities of equal strength are ordered as in
+/// [`RankedPairs`](crate::RankedPairs).
+#[derive(Clone, Debug)]
+pub struct River<T> {
+    pub strength: Strength,
+    pub tie_breaker: TieBreaker<T>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RiverCount<'a, T> {
+    /// Every majority, in the order they were considered.
+    pub log: Vec<Lock<'a, T>>,
+    /// The candidates losing no locked majority, several only when some
+    /// candidates are not connected by any majority.
+    pub winners: Vec<&'a T>,
+    pub tie_breaks: Vec<TieBreak<'a, T>>,
+}
+
+impl<T: Eq + Hash> River<T> {
+    pub fn count<'a>(&self, vote: &'a Vote<T>) -> RiverCount<'a, T> {
+        let n = vote.candidates().len();
+        let (majorities, tie_breaks) = ordered_majorities(vote, self.strength, &self.tie_breaker);
+
+        let name = |i: usize| vote.candidate(CandidateId(i as u32));
+        let mut locked = vec![vec![false; n]; n];
+        let mut defeated_by = vec![None; n];
+        let mut log = vec![];
+        for (winner, loser, strength) in majorities {
+            let majority = Majority { winner: name(winner), loser: name(loser), strength };
+            if let Some(by) = defeated_by[loser] {
+                log.push(Lock::Defeated { majority, by: name(by) });
+            } else if let Some(path) = path(&locked, loser, winner) {
+                log.push(Lock::Skipped { majority, path: path.into_iter().map(name).collect() });
+            } else {
+                locked[winner][loser] = true;
+                defeated_by[loser] = Some(winner);
+                log.push(Lock::Locked(majority));
+            }
+        }
+        RiverCount {
+            log,
+            winners: (0..n).filter(|&i| defeated_by[i].is_none()).map(name).collect(),
+            tie_breaks,
+        }
+    }
+}
+
+impl<T: Eq + Hash> ElectionRule<T> for River<T> {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        let count = self.count(vote);
+        Decision {
+            outcome: Outcome::from_winners(count.winners),
+            scores: None,
+            tie_breaks: count.tie_breaks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::{cycle, tennessee};
+    use crate::{Lock, Majority, River, Strength, TieBreaker};
+
+    #[test]
+    fn defeated_losers_are_skipped() {
+        let vote = tennessee();
+        let priority = TieBreaker::Priority(vec!["Nashville", "Chattanooga", "Knoxville", "Memphis"]);
+        let count = River { strength: Strength::Margins, tie_breaker: priority }.count(&vote);
+        let majority = |winner, loser, strength| Majority { winner, loser, strength };
+        assert_eq!(
+            count.log,
+            vec![
+                Lock::Locked(majority(&"Chattanooga", &"Knoxville", 66)),
+                Lock::Defeated { majority: majority(&"Nashville", &"Knoxville", 36), by: &"Chattanooga" },
+                Lock::Locked(majority(&"Nashville", &"Chattanooga", 36)),
+                Lock::Locked(majority(&"Nashville", &"Memphis", 16)),
+                Lock::Defeated { majority: majority(&"Chattanooga", &"Memphis", 16), by: &"Nashville" },
+                Lock::Defeated { majority: majority(&"Knoxville", &"Memphis", 16), by: &"Nashville" },
+            ]
+        );
+        assert_eq!(count.winners, vec![&"Nashville"]);
+        assert_eq!(count.tie_breaks.len(), 3);
+    }
+
+    #[test]
+    fn cycles_are_skipped() {
+        let vote = cycle();
+        let count = River { strength: Strength::Margins, tie_breaker: TieBreaker::Lot(0) }.count(&vote);
+        assert_eq!(
+            count.log[2],
+            Lock::Skipped { majority: Majority { winner: &"b", loser: &"a", strength: 1 }, path: vec![&"a", &"c", &"b"] }
+        );
+        assert_eq!(count.winners, vec![&"a"]);
+        let count = River { strength: Strength::WinningVotes, tie_breaker: TieBreaker::Lot(0) }.count(&vote);
+        assert_eq!(count.winners, vec![&"c"]);
+    }
+}
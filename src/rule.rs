@@ -0,0 +1,190 @@
+use std::hash::Hash;
+
+use crate::{condorcet_winner_among, CandidateId, Condorcet, OneStage, PreOrder, Tally, TieBreak, TieBreaker, TieBroken, Vote};
+
+/// What an election decided.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Outcome<'a, T> {
+    Winner(&'a T),
+    /// Several candidates are equally placed to win and the rule cannot
+    /// separate them.
+    Tie(Vec<&'a T>),
+    /// The rule does not elect anyone, e.g. there is no Condorcet winner.
+    NoWinner,
+    /// The rule could not be computed, e.g. the election has too many
+    /// candidates for it.
+    Undecided,
+}
+
+impl<'a, T> Outcome<'a, T> {
+    pub fn from_winners(mut winners: Vec<&'a T>) -> Self {
+        match winners.len() {
+            0 => Outcome::NoWinner,
+            1 => Outcome::Winner(winners.remove(0)),
+            _ => Outcome::Tie(winners),
+        }
+    }
+
+    pub fn winner(&self) -> Option<&'a T> {
+        match self {
+            Outcome::Winner(winner) => Some(winner),
+            _ => None,
+        }
+    }
+
+    /// The winner, or every tied candidate.
+    pub fn winners(&self) -> Vec<&'a T> {
+        match self {
+            Outcome::Winner(winner) => vec![winner],
+            Outcome::Tie(winners) => winners.clone(),
+            Outcome::NoWinner | Outcome::Undecided => vec![],
+        }
+    }
+}
+
+/// The result of running an [`ElectionRule`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Decision<'a, T> {
+    pub outcome: Outcome<'a, T>,
+    /// Every candidate with its score, highest first, for rules that score
+    /// candidates.
+    pub scores: Option<Vec<(&'a T, f64)>>,
+    /// Every tie broken along the way, in order.
+    pub tie_breaks: Vec<TieBreak<'a, T>>,
+}
+
+impl<'a, T> Decision<'a, T> {
+    pub fn new(outcome: Outcome<'a, T>, scores: Option<Vec<(&'a T, f64)>>) -> Self {
+        Decision { outcome, scores, tie_breaks: vec![] }
+    }
+}
+
+impl<'a, T> From<Tally<'a, T>> for Decision<'a, T> {
+    fn from(tally: Tally<'a, T>) -> Self {
+        Decision::new(Outcome::from_winners(tally.winners), Some(tally.scores))
+    }
+}
+
+/// A single-winner voting rule, so elections can be run under any rule.
+pub trait ElectionRule<T: Eq + Hash> {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T>;
+
+    /// Settles the ties this rule leaves with `tie_breaker`.
+    fn with_tie_breaker(self, tie_breaker: TieBreaker<T>) -> TieBroken<Self, T>
+        where Self: Sized
+    {
+        TieBroken::new(self, tie_breaker)
+    }
+}
+
+/// A rule producing a full collective ranking of the candidates rather than
+/// just a winner.
+pub trait SocialWelfareFunction<T: Eq + Hash> {
+    fn rank<'a>(&self, vote: &'a Vote<T>) -> PreOrder<&'a T>;
+}
+
+/// See [`OneStage`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Plurality;
+
+impl<T: Eq + Hash + Clone> ElectionRule<T> for Plurality {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        vote.one_stage().into()
+    }
+}
+
+/// Ranks candidates by plurality score.
+impl<T: Eq + Hash + Clone> SocialWelfareFunction<T> for Plurality {
+    fn rank<'a>(&self, vote: &'a Vote<T>) -> PreOrder<&'a T> {
+        PreOrder::from_scores(&vote.one_stage().scores)
+    }
+}
+
+/// Elects the Condorcet winner, and nobody when there is none.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct CondorcetWinner;
+
+impl<T: Eq + Hash + Clone> ElectionRule<T> for CondorcetWinner {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        Decision::new(vote.condorcet_winner().map_or(Outcome::NoWinner, Outcome::Winner), None)
+    }
+}
+
+/// Ranks first the Condorcet winner, then the Condorcet winner of the
+/// remaining candidates, and so on. Once no candidate beats all the remaining
+/// ones, they are tied last.
+impl<T: Eq + Hash + Clone> SocialWelfareFunction<T> for CondorcetWinner {
+    fn rank<'a>(&self, vote: &'a Vote<T>) -> PreOrder<&'a T> {
+        let matrix = vote.pairwise_matrix();
+        let mut remaining: Vec<CandidateId> = vote.ids().collect();
+        let mut classes = vec![];
+        while let Some(winner) = condorcet_winner_among(&matrix, &remaining) {
+            remaining.retain(|&c| c != winner);
+            classes.push(vec![winner]);
+        }
+        classes.push(remaining);
+        PreOrder::weak(classes).map(|id| vote.candidate(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CondorcetWinner, ElectionRule, Outcome, Plurality, PreOrder, SocialWelfareFunction, Vote};
+
+    #[test]
+    fn rules_run_generically() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(35, vec!["a", "b", "c"])
+            .ballot(25, vec!["b", "c", "a"])
+            .ballot(15, vec!["c", "b", "a"])
+            .build()
+            .unwrap();
+        let rules: Vec<Box<dyn ElectionRule<&str>>> = vec![Box::new(Plurality), Box::new(CondorcetWinner)];
+        let outcomes: Vec<_> = rules.iter().map(|rule| rule.elect(&vote).outcome).collect();
+        assert_eq!(outcomes, vec![Outcome::Winner(&"a"), Outcome::Winner(&"b")]);
+        assert_eq!(Plurality.elect(&vote).scores.unwrap()[2], (&"c", 15.));
+    }
+
+    #[test]
+    fn outcomes_tell_ties_from_no_winner() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(1, vec!["a", "b", "c"])
+            .ballot(1, vec!["b", "c", "a"])
+            .ballot(1, vec!["c", "a", "b"])
+            .build()
+            .unwrap();
+        assert_eq!(CondorcetWinner.elect(&vote).outcome, Outcome::NoWinner);
+        assert_eq!(Plurality.elect(&vote).outcome, Outcome::Tie(vec![&"a", &"b", &"c"]));
+    }
+
+    #[test]
+    fn full_rankings() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c", "d"])
+            .ballot(4, vec!["a", "b", "c", "d"])
+            .ballot(3, vec!["b", "c", "a", "d"])
+            .ballot(3, vec!["c", "a", "b", "d"])
+            .build()
+            .unwrap();
+        assert_eq!(
+            Plurality.rank(&vote),
+            PreOrder::weak(vec![vec![&"a"], vec![&"b", &"c"], vec![&"d"]])
+        );
+        // a, b and c form a cycle, all of them beat d.
+        assert_eq!(
+            CondorcetWinner.rank(&vote),
+            PreOrder::weak(vec![vec![&"a", &"b", &"c", &"d"]])
+        );
+
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(4, vec!["b", "a", "c"])
+            .ballot(3, vec!["a", "c", "b"])
+            .ballot(2, vec!["c", "a", "b"])
+            .build()
+            .unwrap();
+        assert_eq!(CondorcetWinner.rank(&vote), PreOrder::from(vec![&"a", &"c", &"b"]));
+    }
+}
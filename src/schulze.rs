@@ -0,0 +1,139 @@
+use std::hash::Hash;
+
+use ndarray::Array2;
+
+use crate::{CandidateId, Decision, ElectionRule, Outcome, PreOrder, SocialWelfareFunction, Vote};
+
+/// How strong a pairwise defeat is.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Strength {
+    /// The number of voters ranking the winner above the loser.
+    WinningVotes,
+    /// The winner's votes minus the loser's.
+    Margins,
+}
+
+impl Strength {
+    /// Strength of the defeat of the `j`-th candidate by the `i`-th one in
+    /// `matrix`, 0 if the `i`-th candidate does not beat the `j`-th.
+    pub(crate) fn of(self, matrix: &Array2<usize>, i: usize, j: usize) -> i64 {
+        let (for_i, for_j) = (matrix[[i, j]] as i64, matrix[[j, i]] as i64);
+        match self {
+            _ if for_i <= for_j => 0,
+            Strength::WinningVotes => for_i,
+            Strength::Margins => for_i - for_j,
+        }
+    }
+}
+
+/// The Schulze (beatpath) method: a candidate beats another if it has a
+/// stronger path of pairwise defeats to it than the other has back, a path
+/// being as strong as its weakest defeat.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Schulze {
+    pub strength: Strength,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SchulzeCount<'a, T> {
+    /// Entry `[i, j]` is the strength of the strongest path from the `i`-th
+    /// candidate to the `j`-th, candidates being indexed by id.
+    pub strongest_paths: Array2<i64>,
+    /// The candidates no other one beats.
+    pub winners: Vec<&'a T>,
+    /// Candidates ranked below every candidate beating them.
+    pub ranking: PreOrder<&'a T>,
+}
+
+impl Schulze {
+    pub fn count<'a, T: Eq + Hash>(&self, vote: &'a Vote<T>) -> SchulzeCount<'a, T> {
+        let matrix = vote.pairwise_matrix();
+        let n = vote.candidates().len();
+        let mut paths = Array2::from_shape_fn((n, n), |(i, j)| self.strength.of(&matrix, i, j));
+        for k in 0..n {
+            for i in (0..n).filter(|&i| i != k) {
+                for j in (0..n).filter(|&j| j != k && j != i) {
+                    let through_k = paths[[i, k]].min(paths[[k, j]]);
+                    if through_k > paths[[i, j]] {
+                        paths[[i, j]] = through_k;
+                    }
+                }
+            }
+        }
+
+        let mut remaining: Vec<CandidateId> = vote.ids().collect();
+        let mut classes = vec![];
+        while !remaining.is_empty() {
+            let unbeaten: Vec<CandidateId> = remaining.iter()
+                .cloned()
+                .filter(|i| remaining.iter().all(|j| paths[[j.index(), i.index()]] <= paths[[i.index(), j.index()]]))
+                .collect();
+            remaining.retain(|id| !unbeaten.contains(id));
+            classes.push(unbeaten.into_iter().map(|id| vote.candidate(id)).collect());
+        }
+        let ranking = PreOrder::weak(classes);
+        SchulzeCount {
+            winners: ranking.top().map_or(vec![], <[_]>::to_vec),
+            strongest_paths: paths,
+            ranking,
+        }
+    }
+}
+
+impl<T: Eq + Hash> ElectionRule<T> for Schulze {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        Decision::new(Outcome::from_winners(self.count(vote).winners), None)
+    }
+}
+
+impl<T: Eq + Hash> SocialWelfareFunction<T> for Schulze {
+    fn rank<'a>(&self, vote: &'a Vote<T>) -> PreOrder<&'a T> {
+        self.count(vote).ranking
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+
+    use crate::fixtures::cycle;
+    use crate::{Condorcet, PreOrder, Schulze, Strength, Vote};
+
+    #[test]
+    fn schulze_without_condorcet_winner() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c", "d", "e"])
+            .ballot(5, vec!["a", "c", "b", "e", "d"])
+            .ballot(5, vec!["a", "d", "e", "c", "b"])
+            .ballot(8, vec!["b", "e", "d", "a", "c"])
+            .ballot(3, vec!["c", "a", "b", "e", "d"])
+            .ballot(7, vec!["c", "a", "e", "b", "d"])
+            .ballot(2, vec!["c", "b", "a", "d", "e"])
+            .ballot(7, vec!["d", "c", "e", "b", "a"])
+            .ballot(8, vec!["e", "b", "a", "d", "c"])
+            .build()
+            .unwrap();
+        assert_eq!(vote.condorcet_winner(), None);
+        let count = Schulze { strength: Strength::WinningVotes }.count(&vote);
+        assert_eq!(
+            count.strongest_paths,
+            array![
+                [0, 28, 28, 30, 24],
+                [25, 0, 28, 33, 24],
+                [25, 29, 0, 29, 24],
+                [25, 28, 28, 0, 24],
+                [25, 28, 28, 31, 0],
+            ]
+        );
+        assert_eq!(count.winners, vec![&"e"]);
+        assert_eq!(count.ranking, PreOrder::from(vec![&"e", &"a", &"c", &"b", &"d"]));
+    }
+
+    #[test]
+    fn strengths_can_disagree() {
+        // The weakest defeat is a > c by winning votes but b > a by margins.
+        let vote = cycle();
+        assert_eq!(Schulze { strength: Strength::WinningVotes }.count(&vote).winners, vec![&"c"]);
+        assert_eq!(Schulze { strength: Strength::Margins }.count(&vote).winners, vec![&"a"]);
+    }
+}
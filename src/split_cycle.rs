@@ -0,0 +1,134 @@
+use std::cmp::Reverse;
+use std::hash::Hash;
+
+use crate::ranked_pairs::path;
+use crate::{CandidateId, Decision, ElectionRule, Majority, Outcome, Strength, Vote};
+
+/// Split Cycle: every cycle of pairwise majorities is split by discarding its
+/// weakest majorities, and the remaining ones are defeats. The winners are
+/// the undefeated candidates.
+///
+/// Equivalently, a majority is a defeat unless its loser has a path back to
+/// its winner made of majorities at least as strong.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SplitCycle {
+    pub strength: Strength,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitCycleCount<'a, T> {
+    /// The majorities that are defeats, strongest first.
+    pub defeats: Vec<Majority<'a, T>>,
+    /// The discarded majorities, strongest first, each with a path from its
+    /// loser back to its winner made of majorities at least as strong.
+    pub discarded: Vec<(Majority<'a, T>, Vec<&'a T>)>,
+    pub winners: Vec<&'a T>,
+}
+
+/// A defeat or a discarded majority, from the winner to the loser, with the
+/// path of the cycle it is discarded in.
+pub(crate) type Judged = (usize, usize, i64, Option<Vec<usize>>);
+
+/// Split Cycle's verdict on every majority among the candidates `among`,
+/// strongest first, `strengths[i][j]` being positive when `i` beats `j`.
+pub(crate) fn split_cycles(strengths: &[Vec<i64>], among: &[usize]) -> Vec<Judged> {
+    let n = strengths.len();
+    let mut majorities: Vec<(usize, usize, i64)> = among.iter()
+        .flat_map(|&i| among.iter().map(move |&j| (i, j)))
+        .map(|(i, j)| (i, j, strengths[i][j]))
+        .filter(|&(_, _, strength)| strength > 0)
+        .collect();
+    majorities.sort_by_key(|&(_, _, strength)| Reverse(strength));
+    majorities.into_iter()
+        .map(|(winner, loser, strength)| {
+            let mut as_strong = vec![vec![false; n]; n];
+            for &i in among {
+                for &j in among {
+                    as_strong[i][j] = strengths[i][j] > 0 && strengths[i][j] >= strength;
+                }
+            }
+            (winner, loser, strength, path(&as_strong, loser, winner))
+        })
+        .collect()
+}
+
+/// The candidates of `among` no defeat of `judged` is against.
+pub(crate) fn undefeated(judged: &[Judged], among: &[usize]) -> Vec<usize> {
+    among.iter()
+        .cloned()
+        .filter(|&c| !judged.iter().any(|(_, loser, _, cycle)| *loser == c && cycle.is_none()))
+        .collect()
+}
+
+impl SplitCycle {
+    pub fn count<'a, T: Eq + Hash>(&self, vote: &'a Vote<T>) -> SplitCycleCount<'a, T> {
+        let matrix = vote.pairwise_matrix();
+        let n = vote.candidates().len();
+        let strengths: Vec<Vec<i64>> = (0..n).map(|i| (0..n).map(|j| self.strength.of(&matrix, i, j)).collect()).collect();
+        let all: Vec<usize> = (0..n).collect();
+        let judged = split_cycles(&strengths, &all);
+
+        let name = |i: usize| vote.candidate(CandidateId(i as u32));
+        let mut count = SplitCycleCount {
+            defeats: vec![],
+            discarded: vec![],
+            winners: undefeated(&judged, &all).into_iter().map(name).collect(),
+        };
+        for (winner, loser, strength, cycle) in judged {
+            let majority = Majority { winner: name(winner), loser: name(loser), strength };
+            match cycle {
+                Some(path) => count.discarded.push((majority, path.into_iter().map(name).collect())),
+                None => count.defeats.push(majority),
+            }
+        }
+        count
+    }
+}
+
+impl<T: Eq + Hash> ElectionRule<T> for SplitCycle {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        Decision::new(Outcome::from_winners(self.count(vote).winners), None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::cycle;
+    use crate::{ElectionRule, Majority, Outcome, SplitCycle, Strength, Vote};
+
+    #[test]
+    fn weakest_majorities_of_cycles_are_discarded() {
+        let vote = cycle();
+        let count = SplitCycle { strength: Strength::Margins }.count(&vote);
+        assert_eq!(
+            count.defeats,
+            vec![
+                Majority { winner: &"c", loser: &"b", strength: 7 },
+                Majority { winner: &"a", loser: &"c", strength: 2 },
+            ]
+        );
+        assert_eq!(
+            count.discarded,
+            vec![(Majority { winner: &"b", loser: &"a", strength: 1 }, vec![&"a", &"c", &"b"])]
+        );
+        assert_eq!(count.winners, vec![&"a"]);
+
+        // With winning votes, a > c is the weakest majority instead.
+        assert_eq!(SplitCycle { strength: Strength::WinningVotes }.count(&vote).winners, vec![&"c"]);
+    }
+
+    #[test]
+    fn several_candidates_can_be_undefeated() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(1, vec!["a", "b", "c"])
+            .ballot(1, vec!["b", "c", "a"])
+            .ballot(1, vec!["c", "a", "b"])
+            .build()
+            .unwrap();
+        let count = SplitCycle { strength: Strength::Margins }.count(&vote);
+        assert!(count.defeats.is_empty());
+        assert_eq!(count.discarded.len(), 3);
+        assert_eq!(SplitCycle { strength: Strength::Margins }.elect(&vote).outcome, Outcome::Tie(vec![&"a", &"b", &"c"]));
+    }
+}
@@ -0,0 +1,172 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::split_cycle::{split_cycles, undefeated};
+use crate::{CandidateId, Decision, ElectionRule, Outcome, SplitCycle, SplitCycleCount, Strength, Vote};
+
+/// Stable Voting: pairs of candidates `(a, b)` are considered from the
+/// largest margin of `a` over `b` down, margins possibly negative, and the
+/// winners are the candidates `a` of the first pairs where `a` wins the
+/// election without `b` and is undefeated under Split Cycle with margins.
+///
+/// The election without `b` is decided the same way, recursively, so the
+/// count takes time exponential in the number of candidates. In elections with
+/// more than [`StableVoting::MAX_CANDIDATES`] candidates, only a unique Split
+/// Cycle winner, such as a Condorcet winner, is elected, and otherwise the
+/// election is undecided.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct StableVoting;
+
+/// Why a candidate wins under Stable Voting.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StableWin<'a, T> {
+    pub winner: &'a T,
+    /// The candidate without whom `winner` still wins.
+    pub without: &'a T,
+    /// The margin of `winner` over `without`.
+    pub margin: i64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StableVotingCount<'a, T> {
+    /// Empty if the pairs were not searched and Split Cycle has several
+    /// winners.
+    pub winners: Vec<&'a T>,
+    /// The deciding pairs, in order of candidate ids, none if there is a
+    /// single candidate or the pairs were not searched.
+    pub wins: Vec<StableWin<'a, T>>,
+    /// Whether the pairs were searched, which they are not above
+    /// [`StableVoting::MAX_CANDIDATES`] candidates.
+    pub searched: bool,
+    /// The Split Cycle defeats among all the candidates, which rule out the
+    /// candidates they are against.
+    pub split_cycle: SplitCycleCount<'a, T>,
+}
+
+/// The deciding pairs `(winner, without)` among the candidates of the subset
+/// `set`, memoized by subset.
+fn stable(margins: &[Vec<i64>], set: usize, memo: &mut HashMap<usize, Vec<(usize, usize)>>) -> Vec<(usize, usize)> {
+    if let Some(wins) = memo.get(&set) {
+        return wins.clone();
+    }
+    let among: Vec<usize> = (0..margins.len()).filter(|i| set & 1 << i != 0).collect();
+    let wins = if among.len() == 1 {
+        vec![(among[0], among[0])]
+    } else {
+        let positive: Vec<Vec<i64>> = margins.iter().map(|row| row.iter().map(|&m| m.max(0)).collect()).collect();
+        let undefeated = undefeated(&split_cycles(&positive, &among), &among);
+        let mut pairs: Vec<(usize, usize)> = among.iter()
+            .flat_map(|&a| among.iter().map(move |&b| (a, b)))
+            .filter(|&(a, b)| a != b && undefeated.contains(&a))
+            .collect();
+        pairs.sort_by_key(|&(a, b)| Reverse(margins[a][b]));
+        let mut wins: Vec<(usize, usize)> = vec![];
+        for (a, b) in pairs {
+            if let Some(&(w, o)) = wins.first() {
+                if margins[w][o] > margins[a][b] {
+                    break;
+                }
+            }
+            if stable(margins, set & !(1 << b), memo).iter().any(|&(w, _)| w == a) {
+                wins.push((a, b));
+            }
+        }
+        wins.sort();
+        wins
+    };
+    memo.insert(set, wins.clone());
+    wins
+}
+
+impl StableVoting {
+    /// The most candidates a count decides between.
+    pub const MAX_CANDIDATES: usize = 16;
+
+    pub fn count<'a, T: Eq + Hash>(&self, vote: &'a Vote<T>) -> StableVotingCount<'a, T> {
+        let matrix = vote.pairwise_matrix();
+        let n = vote.candidates().len();
+        let margins: Vec<Vec<i64>> = (0..n)
+            .map(|i| (0..n).map(|j| matrix[[i, j]] as i64 - matrix[[j, i]] as i64).collect())
+            .collect();
+        let name = |i: usize| vote.candidate(CandidateId(i as u32));
+        let split_cycle = SplitCycle { strength: Strength::Margins }.count(vote);
+        if n > StableVoting::MAX_CANDIDATES {
+            // The winners are always undefeated under Split Cycle.
+            let winners = if split_cycle.winners.len() == 1 { split_cycle.winners.clone() } else { vec![] };
+            return StableVotingCount { winners, wins: vec![], searched: false, split_cycle };
+        }
+        let deciding = stable(&margins, (1 << n) - 1, &mut HashMap::new());
+        let mut winners: Vec<&T> = vec![];
+        for &(a, _) in &deciding {
+            if !winners.contains(&name(a)) {
+                winners.push(name(a));
+            }
+        }
+        // A lone candidate wins without any pair to decide.
+        let wins = deciding.into_iter()
+            .filter(|(a, b)| a != b)
+            .map(|(a, b)| StableWin { winner: name(a), without: name(b), margin: margins[a][b] })
+            .collect();
+        StableVotingCount { winners, wins, searched: true, split_cycle }
+    }
+}
+
+impl<T: Eq + Hash> ElectionRule<T> for StableVoting {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        let count = self.count(vote);
+        if count.winners.is_empty() && !count.searched {
+            return Decision::new(Outcome::Undecided, None);
+        }
+        Decision::new(Outcome::from_winners(count.winners), None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::cycle;
+    use crate::{ElectionRule, Outcome, SplitCycle, StableVoting, StableWin, Strength, Vote};
+
+    #[test]
+    fn winning_without_a_candidate() {
+        // a only wins without b.
+        let vote = cycle();
+        let count = StableVoting.count(&vote);
+        assert_eq!(count.winners, vec![&"a"]);
+        assert_eq!(count.wins, vec![StableWin { winner: &"a", without: &"b", margin: -1 }]);
+        assert_eq!(count.split_cycle.defeats.len(), 2);
+    }
+
+    #[test]
+    fn too_many_candidates() {
+        // 0 is the Condorcet winner.
+        let vote = Vote::builder().candidates(0..64).ballot(1, vec![0]).build().unwrap();
+        let count = StableVoting.count(&vote);
+        assert!(!count.searched && count.wins.is_empty());
+        assert_eq!(count.winners, vec![&0]);
+        assert_eq!(StableVoting.elect(&vote).outcome, Outcome::Winner(&0));
+
+        // 0 and 1 tie, and beat every other candidate.
+        let vote = Vote::builder().candidates(0..64).ballot(1, vec![0]).ballot(1, vec![1]).build().unwrap();
+        let count = StableVoting.count(&vote);
+        assert!(!count.searched && count.winners.is_empty());
+        assert_eq!(count.split_cycle.winners, vec![&0, &1]);
+        assert_eq!(StableVoting.elect(&vote).outcome, Outcome::Undecided);
+    }
+
+    #[test]
+    fn split_cycle_ties_are_settled() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c", "d"])
+            .ballot(1, vec!["b", "a", "d", "c"])
+            .ballot(4, vec!["a", "b", "c", "d"])
+            .ballot(5, vec!["b", "a", "c", "d"])
+            .ballot(2, vec!["c", "a", "d", "b"])
+            .build()
+            .unwrap();
+        assert_eq!(SplitCycle { strength: Strength::Margins }.count(&vote).winners, vec![&"a", &"b"]);
+        let count = StableVoting.count(&vote);
+        assert_eq!(count.winners, vec![&"a"]);
+        assert_eq!(count.wins, vec![StableWin { winner: &"a", without: &"d", margin: 12 }]);
+    }
+}
@@ -0,0 +1,474 @@
+use std::hash::Hash;
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, ToPrimitive, Zero};
+
+use crate::{CandidateId, PreOrder, Stake, TieBreak, TieBreaker, Vote};
+
+/// Votes a candidate needs to be elected.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Quota {
+    /// `floor(votes / (seats + 1)) + 1`, the fewest whole votes no more than
+    /// `seats` candidates can reach. Meek's method, whose votes are not whole,
+    /// uses `votes / (seats + 1)` plus its smallest unit instead.
+    Droop,
+    /// `votes / seats`.
+    Hare,
+}
+
+/// How surpluses are transferred.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum StvMethod {
+    /// Weighted inclusive Gregory: a surplus is transferred from every ballot
+    /// held by the elected candidate, each at its current value times the
+    /// surplus over the candidate's votes. Values are exact.
+    WeightedInclusiveGregory,
+    /// Weighted inclusive Gregory with transfer values truncated to five
+    /// decimal places, as in Scottish local government elections.
+    Scottish,
+    /// Meek's method: elected candidates keep the same fraction of every
+    /// ballot reaching them, adjusted until each holds a quota, and passes
+    /// the rest down, even to candidates elected later. The quota follows
+    /// the votes still counting. Values are kept to nine decimal places.
+    Meek,
+}
+
+/// Single transferable vote, electing `seats` candidates.
+///
+/// A ballot tying several continuing candidates first splits evenly between
+/// them. Blank ballots are not counted, and a candidate without votes never
+/// reaches the quota.
+#[derive(Clone, Debug)]
+pub struct Stv<T> {
+    pub seats: usize,
+    pub quota: Quota,
+    pub method: StvMethod,
+    /// Picks who is excluded among candidates tied last, and who is elected
+    /// among candidates tied for the last seats reached.
+    pub tie_breaker: TieBreaker<T>,
+}
+
+/// What happened to the ballots at a stage of the count.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Transfer<'a, T> {
+    FirstCount,
+    /// The surplus of an elected candidate was transferred.
+    Surplus(&'a T),
+    /// The surpluses of every elected candidate were redistributed, in Meek's
+    /// method.
+    Surpluses,
+    /// A candidate was excluded and its ballots transferred.
+    Exclusion(&'a T),
+}
+
+/// A column of the count sheet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stage<'a, T> {
+    pub transfer: Transfer<'a, T>,
+    /// Votes of every candidate after the transfer, in declaration order.
+    pub votes: Vec<(&'a T, BigRational)>,
+    /// Votes of the ballots with no continuing candidate left.
+    pub exhausted: BigRational,
+    pub quota: BigRational,
+    /// Candidates elected at this stage, the last ones possibly without
+    /// reaching the quota when they are as many as the seats left.
+    pub elected: Vec<&'a T>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StvCount<'a, T> {
+    /// In order of election.
+    pub elected: Vec<&'a T>,
+    pub stages: Vec<Stage<'a, T>>,
+    pub tie_breaks: Vec<TieBreak<'a, T>>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Status {
+    Hopeful,
+    Elected,
+    Excluded,
+}
+
+/// Part of a ballot group, and the candidate currently holding it.
+struct Paper {
+    ballot: usize,
+    value: BigRational,
+    holder: CandidateId,
+}
+
+fn rational(n: usize) -> BigRational {
+    BigRational::from_integer(BigInt::from(n))
+}
+
+fn truncate(x: &BigRational, places: u32) -> BigRational {
+    let scale = BigRational::from_integer(BigInt::from(10).pow(places));
+    (x * &scale).floor() / scale
+}
+
+/// Votes of every stage so far, for the tie breaker.
+fn history<'a, T>(count: &StvCount<'a, T>) -> Vec<Vec<(&'a T, f64)>> {
+    count.stages.iter()
+        .map(|stage| stage.votes.iter().map(|(c, v)| (*c, v.to_f64().unwrap())).collect())
+        .collect()
+}
+
+fn round_up(x: &BigRational, places: u32) -> BigRational {
+    let scale = BigRational::from_integer(BigInt::from(10).pow(places));
+    (x * &scale).ceil() / scale
+}
+
+/// Splits `value` evenly between the hopeful candidates of the first class of
+/// `ballot` holding any, returning it unplaced if there are none.
+fn place(ballot: &PreOrder<CandidateId>, index: usize, value: BigRational, status: &[Status], papers: &mut Vec<Paper>) -> Option<BigRational> {
+    let class = ballot.iter()
+        .map(|class| class.iter().filter(|id| status[id.index()] == Status::Hopeful).collect::<Vec<_>>())
+        .find(|class| !class.is_empty());
+    match class {
+        Some(class) => {
+            let share = value / rational(class.len());
+            papers.extend(class.into_iter().map(|&holder| Paper { ballot: index, value: share.clone(), holder }));
+            None
+        }
+        None => Some(value),
+    }
+}
+
+impl<T> Stv<T> {
+    /// Weighted inclusive Gregory with the Droop quota.
+    pub fn new(seats: usize, tie_breaker: TieBreaker<T>) -> Self {
+        Stv { seats, quota: Quota::Droop, method: StvMethod::WeightedInclusiveGregory, tie_breaker }
+    }
+
+    pub fn quota(mut self, quota: Quota) -> Self {
+        self.quota = quota;
+        self
+    }
+
+    pub fn method(mut self, method: StvMethod) -> Self {
+        self.method = method;
+        self
+    }
+}
+
+impl<T: Eq + Hash> Stv<T> {
+    pub fn count<'a>(&self, vote: &'a Vote<T>) -> StvCount<'a, T> {
+        match self.method {
+            StvMethod::WeightedInclusiveGregory | StvMethod::Scottish => self.count_gregory(vote),
+            StvMethod::Meek => self.count_meek(vote),
+        }
+    }
+
+    /// Elects the hopeful candidates reaching the quota with some votes, most
+    /// votes first, then every hopeful one if they are no more than the seats
+    /// left. Candidates tied for the last seats reached are picked by the tie
+    /// breaker.
+    fn elect<'a>(&self, vote: &'a Vote<T>, votes: &[BigRational], quota: &BigRational, status: &mut [Status], count: &mut StvCount<'a, T>) -> Vec<CandidateId> {
+        let elected = status.iter().filter(|s| **s == Status::Elected).count();
+        let seats = self.seats.saturating_sub(elected);
+        let mut newly: Vec<CandidateId> = vote.ids()
+            .filter(|id| status[id.index()] == Status::Hopeful && votes[id.index()] >= *quota && !votes[id.index()].is_zero())
+            .collect();
+        newly.sort_by(|a, b| votes[b.index()].cmp(&votes[a.index()]));
+        if seats == 0 {
+            newly.clear();
+        } else if newly.len() > seats {
+            let cut = votes[newly[seats - 1].index()].clone();
+            let mut tied: Vec<&T> = newly.iter()
+                .filter(|id| votes[id.index()] == cut)
+                .map(|&id| vote.candidate(id))
+                .collect();
+            newly.retain(|id| votes[id.index()] > cut);
+            let history = history(count);
+            while newly.len() < seats {
+                let tie_break = self.tie_breaker.break_tie(&tied, Stake::Win, &history);
+                tied.retain(|c| *c != tie_break.chosen);
+                newly.push(vote.id_of(tie_break.chosen).unwrap());
+                count.tie_breaks.push(tie_break);
+            }
+        }
+        for id in &newly {
+            status[id.index()] = Status::Elected;
+        }
+        let hopeful: Vec<CandidateId> = vote.ids()
+            .filter(|id| status[id.index()] == Status::Hopeful)
+            .collect();
+        if elected + newly.len() + hopeful.len() <= self.seats {
+            for id in &hopeful {
+                status[id.index()] = Status::Elected;
+            }
+            newly.extend(hopeful);
+        }
+        newly
+    }
+
+    /// Excludes the hopeful candidate with the fewest votes.
+    fn exclude<'a>(&self, vote: &'a Vote<T>, votes: &[BigRational], status: &mut [Status], count: &mut StvCount<'a, T>) -> CandidateId {
+        let hopeful = vote.ids().filter(|id| status[id.index()] == Status::Hopeful);
+        let lowest = hopeful.clone().map(|id| &votes[id.index()]).min().unwrap();
+        let last: Vec<&T> = hopeful.filter(|id| votes[id.index()] == *lowest).map(|id| vote.candidate(id)).collect();
+        let loser = if last.len() == 1 {
+            last[0]
+        } else {
+            let tie_break = self.tie_breaker.break_tie(&last, Stake::Elimination, &history(count));
+            let loser = tie_break.chosen;
+            count.tie_breaks.push(tie_break);
+            loser
+        };
+        let loser = vote.id_of(loser).unwrap();
+        status[loser.index()] = Status::Excluded;
+        loser
+    }
+
+    fn count_gregory<'a>(&self, vote: &'a Vote<T>) -> StvCount<'a, T> {
+        let n = vote.candidates().len();
+        let mut status = vec![Status::Hopeful; n];
+        let mut papers = vec![];
+        let mut exhausted = BigRational::zero();
+        let mut valid = 0;
+        for (i, (voters, ballot)) in vote.ballots().iter().enumerate() {
+            if !ballot.is_empty() {
+                valid += voters;
+                place(ballot, i, rational(*voters), &status, &mut papers);
+            }
+        }
+        let quota = match self.quota {
+            Quota::Droop => rational(valid / (self.seats + 1) + 1),
+            Quota::Hare => rational(valid) / rational(self.seats.max(1)),
+        };
+        // Votes of elected candidates whose surplus was transferred.
+        let mut settled: Vec<Option<BigRational>> = vec![None; n];
+        let mut pending: Vec<CandidateId> = vec![];
+        let mut count = StvCount { elected: vec![], stages: vec![], tie_breaks: vec![] };
+        let mut transfer = Transfer::FirstCount;
+        loop {
+            let mut votes = settled.iter()
+                .map(|v| v.clone().unwrap_or_else(BigRational::zero))
+                .collect::<Vec<_>>();
+            for paper in &papers {
+                votes[paper.holder.index()] += &paper.value;
+            }
+            let newly = self.elect(vote, &votes, &quota, &mut status, &mut count);
+            pending.extend(newly.iter().filter(|id| votes[id.index()] > quota));
+            let newly: Vec<&T> = newly.into_iter().map(|id| vote.candidate(id)).collect();
+            count.elected.extend(&newly);
+            count.stages.push(Stage {
+                transfer,
+                votes: vote.candidates().iter().zip(votes.iter().cloned()).collect(),
+                exhausted: exhausted.clone(),
+                quota: quota.clone(),
+                elected: newly,
+            });
+            if count.elected.len() >= self.seats || status.iter().all(|s| *s != Status::Hopeful) {
+                return count;
+            }
+
+            let moving: CandidateId;
+            let mut ratio = None;
+            if let Some((i, _)) = pending.iter().enumerate().max_by(|(i, a), (j, b)| {
+                votes[a.index()].cmp(&votes[b.index()]).then(j.cmp(i))
+            }) {
+                moving = pending.remove(i);
+                ratio = Some((&votes[moving.index()] - &quota) / &votes[moving.index()]);
+                settled[moving.index()] = Some(quota.clone());
+                transfer = Transfer::Surplus(vote.candidate(moving));
+            } else {
+                moving = self.exclude(vote, &votes, &mut status, &mut count);
+                transfer = Transfer::Exclusion(vote.candidate(moving));
+            }
+            let (held, kept): (Vec<Paper>, Vec<Paper>) = papers.into_iter().partition(|p| p.holder == moving);
+            papers = kept;
+            for paper in held {
+                let value = match &ratio {
+                    None => paper.value,
+                    Some(ratio) if self.method == StvMethod::Scottish => {
+                        let voters = rational(vote.ballots()[paper.ballot].0);
+                        truncate(&(paper.value / &voters * ratio), 5) * voters
+                    }
+                    Some(ratio) => paper.value * ratio,
+                };
+                let ballot = &vote.ballots()[paper.ballot].1;
+                if let Some(lost) = place(ballot, paper.ballot, value, &status, &mut papers) {
+                    exhausted += lost;
+                }
+            }
+        }
+    }
+
+    fn count_meek<'a>(&self, vote: &'a Vote<T>) -> StvCount<'a, T> {
+        const PLACES: u32 = 9;
+        let unit = BigRational::new(BigInt::one(), BigInt::from(10).pow(PLACES));
+        let tolerance = BigRational::new(BigInt::one(), BigInt::from(100_000));
+        let n = vote.candidates().len();
+        let mut status = vec![Status::Hopeful; n];
+        let mut keep = vec![BigRational::one(); n];
+        let valid = rational(vote.ballots().iter()
+            .filter(|(_, ballot)| !ballot.is_empty())
+            .map(|(voters, _)| voters)
+            .sum());
+        let mut count = StvCount { elected: vec![], stages: vec![], tie_breaks: vec![] };
+        let mut transfer = Transfer::FirstCount;
+        loop {
+            let mut votes;
+            let mut excess;
+            let mut quota;
+            let mut iterations = 0;
+            loop {
+                votes = vec![BigRational::zero(); n];
+                excess = BigRational::zero();
+                for (voters, ballot) in vote.ballots() {
+                    let mut remaining = rational(*voters);
+                    for class in ballot.iter() {
+                        let class: Vec<_> = class.iter().filter(|id| status[id.index()] != Status::Excluded).collect();
+                        if class.is_empty() {
+                            continue;
+                        }
+                        let share = &remaining / rational(class.len());
+                        let mut passed = BigRational::zero();
+                        for id in class {
+                            let kept = truncate(&(&share * &keep[id.index()]), PLACES);
+                            passed += &share - &kept;
+                            votes[id.index()] += kept;
+                        }
+                        remaining = passed;
+                    }
+                    if !ballot.is_empty() {
+                        excess += remaining;
+                    }
+                }
+                quota = match self.quota {
+                    Quota::Droop => truncate(&((&valid - &excess) / rational(self.seats + 1)), PLACES) + &unit,
+                    Quota::Hare => truncate(&((&valid - &excess) / rational(self.seats.max(1))), PLACES),
+                };
+                let elected = vote.ids().filter(|id| status[id.index()] == Status::Elected);
+                let surplus: BigRational = elected.clone()
+                    .map(|id| &votes[id.index()] - &quota)
+                    .filter(|s| *s > BigRational::zero())
+                    .sum();
+                let reached = vote.ids().any(|id| status[id.index()] == Status::Hopeful && votes[id.index()] >= quota);
+                iterations += 1;
+                if reached || surplus < tolerance || iterations > 1000 {
+                    break;
+                }
+                for id in elected {
+                    keep[id.index()] = round_up(&(&keep[id.index()] * &quota / &votes[id.index()]), PLACES);
+                }
+            }
+            let newly: Vec<&T> = self.elect(vote, &votes, &quota, &mut status, &mut count)
+                .into_iter()
+                .map(|id| vote.candidate(id))
+                .collect();
+            count.elected.extend(&newly);
+            let elected_now = !newly.is_empty();
+            count.stages.push(Stage {
+                transfer,
+                votes: vote.candidates().iter().zip(votes.iter().cloned()).collect(),
+                exhausted: excess,
+                quota,
+                elected: newly,
+            });
+            if count.elected.len() >= self.seats || status.iter().all(|s| *s != Status::Hopeful) {
+                return count;
+            }
+            transfer = if elected_now {
+                Transfer::Surpluses
+            } else {
+                let loser = self.exclude(vote, &votes, &mut status, &mut count);
+                keep[loser.index()] = BigRational::zero();
+                Transfer::Exclusion(vote.candidate(loser))
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_traits::ToPrimitive;
+
+    use crate::{Quota, Stake, Stv, StvMethod, TieBreaker, Transfer, Vote};
+
+    use super::rational;
+
+    #[test]
+    fn surplus_transfer() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c", "d"])
+            .ballot(10, vec!["a", "b"])
+            .ballot(4, vec!["b"])
+            .ballot(3, vec!["c", "d"])
+            .ballot(2, vec!["d", "c"])
+            .build()
+            .unwrap();
+        let count = Stv::new(2, TieBreaker::Lot(0)).count(&vote);
+        assert_eq!(count.elected, vec![&"a", &"b"]);
+        assert_eq!(count.stages.len(), 2);
+        assert_eq!(count.stages[0].quota, rational(7));
+        assert_eq!(count.stages[1].transfer, Transfer::Surplus(&"a"));
+        assert_eq!(count.stages[1].votes[1], (&"b", rational(7)));
+
+        // The larger Hare quota leaves a with a smaller surplus for b.
+        let count = Stv::new(2, TieBreaker::Lot(0)).quota(Quota::Hare).count(&vote);
+        assert_eq!(count.stages[0].quota, rational(19) / rational(2));
+        assert_eq!(count.stages[1].votes[1], (&"b", rational(9) / rational(2)));
+        assert_eq!(count.stages[2].transfer, Transfer::Exclusion(&"d"));
+        assert_eq!(count.stages[3].transfer, Transfer::Exclusion(&"b"));
+        assert_eq!(count.elected, vec![&"a", &"c"]);
+    }
+
+    #[test]
+    fn scottish_truncates_transfer_values() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(11, vec!["a", "b"])
+            .ballot(5, vec!["c"])
+            .build()
+            .unwrap();
+        let stv = Stv::new(2, TieBreaker::Priority(vec!["b", "c"]));
+        let exact = stv.clone().count(&vote);
+        assert_eq!(exact.stages[1].votes[1], (&"b", rational(5)));
+        assert_eq!(exact.elected, vec![&"a", &"b"]);
+
+        let scottish = stv.method(StvMethod::Scottish).count(&vote);
+        assert_eq!(scottish.stages[1].votes[1].1.to_f64(), Some(4.99994));
+        assert_eq!(scottish.stages[2].transfer, Transfer::Exclusion(&"b"));
+        assert_eq!(scottish.elected, vec![&"a", &"c"]);
+    }
+
+    #[test]
+    fn meek_lowers_the_quota_as_ballots_exhaust() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c", "d"])
+            .ballot(6, vec!["a"])
+            .ballot(3, vec!["b", "d"])
+            .ballot(3, vec!["c"])
+            .ballot(1, vec!["d", "b"])
+            .build()
+            .unwrap();
+        let count = Stv::new(2, TieBreaker::Lot(0)).method(StvMethod::Meek).count(&vote);
+        assert_eq!(count.stages[0].elected, vec![&"a"]);
+        assert_eq!(count.stages[1].transfer, Transfer::Surpluses);
+        // a keeps 7/12 of its ballots, 3.5 votes, as much as the new quota.
+        let quota = count.stages[1].quota.to_f64().unwrap();
+        assert!((quota - 3.5).abs() < 1e-4);
+        assert_eq!(count.stages[2].transfer, Transfer::Exclusion(&"d"));
+        assert_eq!(count.elected, vec![&"a", &"b"]);
+    }
+
+    #[test]
+    fn blank_ballots_reach_no_quota() {
+        // The Hare quota is zero, which nobody reaches without votes, so
+        // candidates are excluded through the tie breaker.
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(5, vec![])
+            .build()
+            .unwrap();
+        for method in [StvMethod::WeightedInclusiveGregory, StvMethod::Meek].iter() {
+            let count = Stv::new(1, TieBreaker::Priority(vec!["c"])).quota(Quota::Hare).method(*method).count(&vote);
+            assert_eq!(count.elected, vec![&"c"]);
+            assert_eq!(count.tie_breaks.len(), 2);
+            assert!(count.tie_breaks.iter().all(|t| t.stake == Stake::Elimination));
+        }
+    }
+}
@@ -0,0 +1,291 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use crate::{Decision, ElectionRule, Outcome, Vote};
+
+/// What a tie is about.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Stake {
+    /// Picking the winner among the tied candidates.
+    Win,
+    /// Picking which of the tied candidates is eliminated.
+    Elimination,
+}
+
+/// Picks one of the tied candidates, returning its index in them. Returning
+/// an index past the tied candidates panics.
+pub type TieCallback<T> = Rc<dyn Fn(&[&T], Stake) -> usize>;
+
+/// How ties between candidates are broken.
+pub enum TieBreaker<T> {
+    /// Candidates listed first win ties and survive eliminations. Candidates
+    /// not listed come after every listed one.
+    Priority(Vec<T>),
+    /// Drawing lots from a generator seeded with the given value, the round
+    /// and the tied candidates, so the same seed always draws the same
+    /// candidate from the same tie.
+    Lot(u64),
+    /// Looks back through previous rounds, most recent first, for one where
+    /// the tied candidates scored differently, and favours the higher score.
+    /// Ties that no round separates go to the inner strategy.
+    Backward(Box<TieBreaker<T>>),
+    /// Asks the caller, who returns the index of the chosen candidate in the
+    /// tied ones.
+    Callback(TieCallback<T>),
+}
+
+impl<T: Clone> Clone for TieBreaker<T> {
+    fn clone(&self) -> Self {
+        match self {
+            TieBreaker::Priority(order) => TieBreaker::Priority(order.clone()),
+            TieBreaker::Lot(seed) => TieBreaker::Lot(*seed),
+            TieBreaker::Backward(fallback) => TieBreaker::Backward(fallback.clone()),
+            TieBreaker::Callback(callback) => TieBreaker::Callback(callback.clone()),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for TieBreaker<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TieBreaker::Priority(order) => f.debug_tuple("Priority").field(order).finish(),
+            TieBreaker::Lot(seed) => f.debug_tuple("Lot").field(seed).finish(),
+            TieBreaker::Backward(fallback) => f.debug_tuple("Backward").field(fallback).finish(),
+            TieBreaker::Callback(_) => f.write_str("Callback"),
+        }
+    }
+}
+
+/// The strategy that settled a tie, with what is needed to replay it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Resolution {
+    Priority,
+    Lot { seed: u64, draw: u64 },
+    /// Settled by the scores of the given round, counted from 0.
+    Backward { round: usize },
+    Callback,
+}
+
+/// A tie and how it was broken.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TieBreak<'a, T> {
+    pub stake: Stake,
+    pub tied: Vec<&'a T>,
+    pub chosen: &'a T,
+    pub resolution: Resolution,
+}
+
+impl<T: Eq + Hash> TieBreaker<T> {
+    /// Picks one of `tied`, which must not be empty. `history` holds the
+    /// scores of the previous rounds, oldest first, for rules counting in
+    /// several rounds.
+    pub fn break_tie<'a>(&self, tied: &[&'a T], stake: Stake, history: &[Vec<(&'a T, f64)>]) -> TieBreak<'a, T> {
+        assert!(!tied.is_empty(), "breaking a tie between no candidates");
+        let (chosen, resolution) = self.choose(tied, stake, history);
+        TieBreak { stake, tied: tied.to_vec(), chosen, resolution }
+    }
+
+    fn choose<'a>(&self, tied: &[&'a T], stake: Stake, history: &[Vec<(&'a T, f64)>]) -> (&'a T, Resolution) {
+        match self {
+            TieBreaker::Priority(order) => {
+                let priority = |c: &&T| order.iter().position(|o| o == *c).unwrap_or(order.len());
+                let chosen = match stake {
+                    Stake::Win => tied.iter().cloned().min_by_key(priority),
+                    Stake::Elimination => tied.iter().cloned().max_by_key(priority),
+                };
+                (chosen.unwrap(), Resolution::Priority)
+            }
+            TieBreaker::Lot(seed) => {
+                // Mixing in the round and the tied candidates keeps the draws
+                // of different ties apart, even within a round.
+                let mut hasher = Fnv(history.len() as u64 ^ FNV_OFFSET);
+                tied.hash(&mut hasher);
+                let draw = split_mix(seed ^ hasher.finish());
+                (tied[(draw % tied.len() as u64) as usize], Resolution::Lot { seed: *seed, draw })
+            }
+            TieBreaker::Backward(fallback) => {
+                let mut remaining = tied.to_vec();
+                let mut deciding_round = None;
+                for (round, scores) in history.iter().enumerate().rev() {
+                    let score = |c: &T| scores.iter().find(|(s, _)| *s == c).map(|&(_, score)| score);
+                    let round_scores: Option<Vec<f64>> = remaining.iter().map(|c| score(c)).collect();
+                    let round_scores = match round_scores {
+                        Some(round_scores) => round_scores,
+                        None => continue,
+                    };
+                    let target = match stake {
+                        Stake::Win => round_scores.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+                        Stake::Elimination => round_scores.iter().cloned().fold(f64::INFINITY, f64::min),
+                    };
+                    let kept: Vec<&T> = remaining.iter()
+                        .zip(&round_scores)
+                        .filter(|(_, &s)| crate::same_score(s, target))
+                        .map(|(c, _)| *c)
+                        .collect();
+                    if kept.len() < remaining.len() {
+                        remaining = kept;
+                        deciding_round = Some(round);
+                        if remaining.len() == 1 {
+                            break;
+                        }
+                    }
+                }
+                match (remaining.as_slice(), deciding_round) {
+                    ([chosen], Some(round)) => (chosen, Resolution::Backward { round }),
+                    _ => fallback.choose(&remaining, stake, history),
+                }
+            }
+            TieBreaker::Callback(callback) => {
+                let index = callback(tied, stake);
+                assert!(index < tied.len(), "tie-breaking callback chose index {} of {} tied candidates", index, tied.len());
+                (tied[index], Resolution::Callback)
+            }
+        }
+    }
+}
+
+/// SplitMix64, a small generator whose output only depends on the seed, on
+/// every platform and across releases.
+fn split_mix(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+const FNV_OFFSET: u64 = 0xCBF2_9CE4_8422_2325;
+
+/// FNV-1a, hashing integers as little-endian bytes of a fixed width so that
+/// lots are drawn the same way on every platform.
+struct Fnv(u64);
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x100_0000_01B3);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+}
+
+/// A rule whose ties are broken by a [`TieBreaker`], see
+/// [`ElectionRule::with_tie_breaker`].
+#[derive(Clone, Debug)]
+pub struct TieBroken<R, T> {
+    pub rule: R,
+    pub tie_breaker: TieBreaker<T>,
+    _candidate: PhantomData<T>,
+}
+
+impl<R, T> TieBroken<R, T> {
+    pub fn new(rule: R, tie_breaker: TieBreaker<T>) -> Self {
+        TieBroken { rule, tie_breaker, _candidate: PhantomData }
+    }
+}
+
+impl<T: Eq + Hash, R: ElectionRule<T>> ElectionRule<T> for TieBroken<R, T> {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        let mut decision = self.rule.elect(vote);
+        if let Outcome::Tie(tied) = &decision.outcome {
+            let tie_break = self.tie_breaker.break_tie(tied, Stake::Win, &[]);
+            decision.outcome = Outcome::Winner(tie_break.chosen);
+            decision.tie_breaks.push(tie_break);
+        }
+        decision
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::{ElectionRule, Outcome, Plurality, Resolution, Stake, TieBreaker, Vote};
+
+    fn tied_vote() -> Vote<&'static str> {
+        Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(2, vec!["a", "b", "c"])
+            .ballot(2, vec!["b", "c", "a"])
+            .ballot(2, vec!["c", "a", "b"])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn rules_break_ties() {
+        let vote = tied_vote();
+        let decision = Plurality.with_tie_breaker(TieBreaker::Priority(vec!["c", "b"])).elect(&vote);
+        assert_eq!(decision.outcome, Outcome::Winner(&"c"));
+        assert_eq!(decision.tie_breaks[0].tied, vec![&"a", &"b", &"c"]);
+        assert_eq!(decision.tie_breaks[0].resolution, Resolution::Priority);
+
+        let lot = Plurality.with_tie_breaker(TieBreaker::Lot(42));
+        assert_eq!(lot.elect(&vote), lot.elect(&vote));
+
+        let callback = TieBreaker::Callback(Rc::new(|tied: &[&&str], _| tied.len() - 1));
+        assert_eq!(Plurality.with_tie_breaker(callback).elect(&vote).outcome, Outcome::Winner(&"c"));
+    }
+
+    #[test]
+    fn priority_eliminates_the_last_listed() {
+        let tie_breaker = TieBreaker::Priority(vec!["b"]);
+        let tie_break = tie_breaker.break_tie(&[&"a", &"b", &"c"], Stake::Elimination, &[]);
+        assert_eq!(tie_break.chosen, &"c");
+    }
+
+    #[test]
+    fn lots_differ_between_ties_of_a_round() {
+        let tie_breaker = TieBreaker::Lot(7);
+        let draw = |tied: &[&&'static str]| tie_breaker.break_tie(tied, Stake::Win, &[]).resolution;
+        assert_ne!(draw(&[&"a", &"b", &"c"]), draw(&[&"a", &"b"]));
+        assert_eq!(draw(&[&"a", &"b"]), draw(&[&"a", &"b"]));
+    }
+
+    #[test]
+    #[should_panic(expected = "tie-breaking callback chose index 5 of 2 tied candidates")]
+    fn callbacks_choose_a_tied_candidate() {
+        let callback = TieBreaker::Callback(Rc::new(|_: &[&&str], _| 5));
+        callback.break_tie(&[&"a", &"b"], Stake::Win, &[]);
+    }
+
+    #[test]
+    fn backward_looks_at_previous_rounds() {
+        let history = vec![
+            vec![(&"a", 3.), (&"b", 1.), (&"c", 2.)],
+            vec![(&"a", 4.), (&"b", 4.), (&"c", 2.)],
+        ];
+        let tie_breaker = TieBreaker::Backward(Box::new(TieBreaker::Priority(vec![])));
+        let tie_break = tie_breaker.break_tie(&[&"a", &"b"], Stake::Elimination, &history);
+        assert_eq!(tie_break.chosen, &"b");
+        assert_eq!(tie_break.resolution, Resolution::Backward { round: 0 });
+
+        let tie_break = tie_breaker.break_tie(&[&"a", &"b"], Stake::Win, &history[1..]);
+        assert_eq!(tie_break.chosen, &"a");
+        assert_eq!(tie_break.resolution, Resolution::Priority);
+    }
+}
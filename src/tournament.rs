@@ -0,0 +1,332 @@
+use std::hash::Hash;
+
+use num_rational::BigRational;
+use num_traits::{One, Zero};
+
+use crate::{Decision, ElectionRule, Outcome, Vote};
+
+/// When a candidate `x` covers a candidate `y`, in which case `y` is not in
+/// the uncovered set. The variants agree when there are no pairwise ties.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Covering {
+    /// `x` beats `y` and every candidate `y` beats.
+    Gillies,
+    /// Every candidate beating `x` beats `y`, and some candidate beating `y`
+    /// does not beat `x`.
+    Fishburn,
+    /// `x` beats `y` and every candidate `y` beats, and ties or beats every
+    /// candidate `y` ties.
+    McKelvey,
+}
+
+impl Covering {
+    /// Whether `x` covers `y` in the majority relation `beats` restricted to
+    /// `within`.
+    fn covers(self, beats: &[Vec<bool>], within: &[usize], x: usize, y: usize) -> bool {
+        let beaten_by_y_beaten_by_x = within.iter().all(|&z| !beats[y][z] || beats[x][z]);
+        match self {
+            Covering::Gillies => beats[x][y] && beaten_by_y_beaten_by_x,
+            Covering::Fishburn => {
+                within.iter().all(|&z| !beats[z][x] || beats[z][y])
+                    && within.iter().any(|&z| beats[z][y] && !beats[z][x])
+            }
+            Covering::McKelvey => {
+                beats[x][y]
+                    && beaten_by_y_beaten_by_x
+                    && within.iter().all(|&z| beats[z][y] || !beats[z][x])
+            }
+        }
+    }
+}
+
+/// Tournament solutions: sets of candidates chosen from the majority relation
+/// alone, ignoring by how much candidates win their contests. Pairwise ties
+/// count as neither candidate beating the other.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum TournamentSolution {
+    /// The Smith set, see [`Vote::smith_set`].
+    TopCycle,
+    /// The candidates no other candidate covers.
+    UncoveredSet(Covering),
+    /// The tops of the maximal chains of candidates each beating all the ones
+    /// below. Exponential in the number of candidates, see
+    /// [`TournamentSolution::MAX_CANDIDATES`].
+    Banks,
+    /// The smallest set of candidates uncovered among themselves and covering,
+    /// with [`Covering::Gillies`], every candidate added to them. It is unique
+    /// without pairwise ties; with ties, the first one in candidate order is
+    /// chosen. Exponential in the number of candidates, see
+    /// [`TournamentSolution::MAX_CANDIDATES`].
+    MinimalCoveringSet,
+    /// The candidates some optimal strategy plays in the symmetric zero-sum
+    /// game where picking the winner of a pairwise contest pays 1 and the
+    /// loser -1. Computed exactly with rational linear programming.
+    BipartisanSet,
+}
+
+impl TournamentSolution {
+    /// The most candidates [`TournamentSolution::Banks`] and
+    /// [`TournamentSolution::MinimalCoveringSet`] choose from, unless one of
+    /// them beats every other, which both choose alone.
+    pub const MAX_CANDIDATES: usize = 20;
+
+    /// The chosen candidates, in candidate order, or `None` if there are too
+    /// many candidates to compute the solution.
+    pub fn choose<'a, T: Eq + Hash>(&self, vote: &'a Vote<T>) -> Option<Vec<&'a T>> {
+        let beats = vote.majority_graph();
+        let n = beats.len();
+        let all: Vec<usize> = (0..n).collect();
+        let chosen = match *self {
+            TournamentSolution::TopCycle => return Some(vote.smith_set()),
+            TournamentSolution::UncoveredSet(covering) => uncovered(covering, &beats, &all),
+            TournamentSolution::Banks | TournamentSolution::MinimalCoveringSet => {
+                match (0..n).find(|&x| (0..n).all(|y| y == x || beats[x][y])) {
+                    Some(winner) => vec![winner],
+                    None if n > TournamentSolution::MAX_CANDIDATES => return None,
+                    None if *self == TournamentSolution::Banks => banks(&beats),
+                    None => minimal_covering_set(&beats),
+                }
+            }
+            TournamentSolution::BipartisanSet => bipartisan(&beats),
+        };
+        Some(chosen.into_iter().map(|i| &vote.candidates()[i]).collect())
+    }
+}
+
+fn uncovered(covering: Covering, beats: &[Vec<bool>], within: &[usize]) -> Vec<usize> {
+    within.iter()
+        .cloned()
+        .filter(|&y| !within.iter().any(|&x| x != y && covering.covers(beats, within, x, y)))
+        .collect()
+}
+
+fn banks(beats: &[Vec<bool>]) -> Vec<usize> {
+    let n = beats.len();
+    // A chain, best first, whose top is in the Banks set if no candidate
+    // beats all of it.
+    fn maximal(beats: &[Vec<bool>], chain: &mut Vec<usize>) -> bool {
+        let n = beats.len();
+        if !(0..n).any(|z| chain.iter().all(|&c| beats[z][c])) {
+            return true;
+        }
+        for y in 0..n {
+            if chain.iter().all(|&c| beats[c][y]) {
+                chain.push(y);
+                if maximal(beats, chain) {
+                    return true;
+                }
+                chain.pop();
+            }
+        }
+        false
+    }
+    (0..n).filter(|&x| maximal(beats, &mut vec![x])).collect()
+}
+
+/// The subsets of `k` of the candidates `0..n`, `k >= 1`, as bit sets in
+/// increasing order.
+fn subsets(n: usize, k: usize) -> impl Iterator<Item = usize> {
+    std::iter::successors(Some((1usize << k) - 1), |&s| {
+        // The next larger number with as many bits set.
+        let low = s & s.wrapping_neg();
+        let ripple = s + low;
+        Some(ripple | (((s ^ ripple) >> 2) / low))
+    })
+    .take_while(move |&s| s < 1 << n)
+}
+
+fn minimal_covering_set(beats: &[Vec<bool>]) -> Vec<usize> {
+    let n = beats.len();
+    (1..=n)
+        .flat_map(|k| subsets(n, k))
+        .map(|s| (0..n).filter(|x| s & 1 << x != 0).collect::<Vec<_>>())
+        .find(|set| {
+            uncovered(Covering::Gillies, beats, set) == *set
+                && (0..n).filter(|x| !set.contains(x)).all(|x| {
+                    let mut with = set.clone();
+                    with.push(x);
+                    set.iter().any(|&y| Covering::Gillies.covers(beats, &with, y, x))
+                })
+        })
+        .unwrap_or_default()
+}
+
+fn bipartisan(beats: &[Vec<bool>]) -> Vec<usize> {
+    let n = beats.len();
+    let payoff = |i: usize, j: usize| -> BigRational {
+        if beats[i][j] {
+            BigRational::one()
+        } else if beats[j][i] {
+            -BigRational::one()
+        } else {
+            BigRational::zero()
+        }
+    };
+    // Strategies p with sum(p) <= 1 gaining at least 0 against every pure
+    // strategy, scaled up, are exactly the optimal ones since the game is
+    // symmetric. A candidate is played by some optimal strategy when its
+    // weight can be positive.
+    let mut constraints: Vec<Vec<BigRational>> = (0..n).map(|j| (0..n).map(|i| -payoff(i, j)).collect()).collect();
+    constraints.push(vec![BigRational::one(); n]);
+    let mut bounds = vec![BigRational::zero(); n];
+    bounds.push(BigRational::one());
+    (0..n)
+        .filter(|&k| {
+            let objective = (0..n).map(|i| if i == k { BigRational::one() } else { BigRational::zero() }).collect();
+            maximize(&constraints, &bounds, objective).expect("bounded linear program").0 > BigRational::zero()
+        })
+        .collect()
+}
+
+/// The maximum of `objective · x` under `constraints · x <= bounds` and
+/// `x >= 0`, with `bounds >= 0`, and an optimal solution of the dual problem,
+/// one value per constraint. `None` if the maximum is infinite. Simplex,
+/// switching to Bland's rule once pivots stall so it terminates on degenerate
+/// problems.
+pub(crate) fn maximize(constraints: &[Vec<BigRational>], bounds: &[BigRational], objective: Vec<BigRational>) -> Option<(BigRational, Vec<BigRational>)> {
+    let (m, n) = (constraints.len(), objective.len());
+    // Rows are the constraints then the objective, columns the variables,
+    // the slacks then the right-hand side.
+    let mut tableau: Vec<Vec<BigRational>> = constraints.iter()
+        .zip(bounds)
+        .enumerate()
+        .map(|(i, (row, bound))| {
+            let mut row = row.clone();
+            row.extend((0..m).map(|s| if s == i { BigRational::one() } else { BigRational::zero() }));
+            row.push(bound.clone());
+            row
+        })
+        .collect();
+    let mut last: Vec<BigRational> = objective.into_iter().map(|c| -c).collect();
+    last.extend((0..=m).map(|_| BigRational::zero()));
+    tableau.push(last);
+    let mut basis: Vec<usize> = (n..n + m).collect();
+
+    // The most negative reduced cost enters, until as many pivots as there
+    // are constraints have left the objective unchanged, when Bland's rule
+    // takes over.
+    let mut stalled = 0;
+    loop {
+        let negative = (0..n + m).filter(|&j| tableau[m][j] < BigRational::zero());
+        let entering = if stalled < m {
+            negative.min_by(|&i, &j| tableau[m][i].cmp(&tableau[m][j]).then(i.cmp(&j)))
+        } else {
+            negative.min()
+        };
+        let entering = match entering {
+            Some(entering) => entering,
+            None => break,
+        };
+        let leaving = (0..m)
+            .filter(|&i| tableau[i][entering] > BigRational::zero())
+            .min_by(|&i, &j| {
+                let ratio = |r: usize| &tableau[r][n + m] / &tableau[r][entering];
+                ratio(i).cmp(&ratio(j)).then(basis[i].cmp(&basis[j]))
+            })?;
+        if tableau[leaving][n + m].is_zero() {
+            stalled += 1;
+        }
+        let pivot = tableau[leaving][entering].clone();
+        for value in tableau[leaving].iter_mut() {
+            *value /= &pivot;
+        }
+        // Tableaux are sparse: only the columns where the pivot row is nonzero
+        // change.
+        let pivot_row: Vec<(usize, BigRational)> = tableau[leaving].iter()
+            .cloned()
+            .enumerate()
+            .filter(|(_, p)| !p.is_zero())
+            .collect();
+        for (i, row) in tableau.iter_mut().enumerate() {
+            if i != leaving && !row[entering].is_zero() {
+                let factor = row[entering].clone();
+                for (j, p) in &pivot_row {
+                    row[*j] -= &factor * p;
+                }
+            }
+        }
+        basis[leaving] = entering;
+    }
+    Some((tableau[m][n + m].clone(), tableau[m][n..n + m].to_vec()))
+}
+
+/// Elects the chosen candidates, a tie if there are several.
+impl<T: Eq + Hash> ElectionRule<T> for TournamentSolution {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        Decision::new(self.choose(vote).map_or(Outcome::Undecided, Outcome::from_winners), None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Covering, ElectionRule, Outcome, TournamentSolution, Vote};
+
+    /// An election whose majority relation is exactly `edges`: each is made
+    /// by two ballots cancelling each other on every other contest.
+    fn tournament(candidates: &[&'static str], edges: &[(&'static str, &'static str)]) -> Vote<&'static str> {
+        let mut builder = Vote::builder().candidates(candidates.to_vec());
+        for &(x, y) in edges {
+            let others: Vec<_> = candidates.iter().cloned().filter(|&c| c != x && c != y).collect();
+            let mut down = vec![x, y];
+            down.extend(others.iter().cloned());
+            let mut up: Vec<_> = others.into_iter().rev().collect();
+            up.extend(vec![x, y]);
+            builder = builder.ballot(1, down).ballot(1, up);
+        }
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn nested_solutions() {
+        let vote = tournament(
+            &["a", "b", "c", "d", "e"],
+            &[("a", "b"), ("a", "c"), ("b", "d"), ("c", "b"), ("c", "e"),
+              ("d", "a"), ("d", "c"), ("e", "a"), ("e", "b"), ("e", "d")],
+        );
+        let choose = |solution: TournamentSolution| solution.choose(&vote).unwrap();
+        assert_eq!(choose(TournamentSolution::TopCycle), vec![&"a", &"b", &"c", &"d", &"e"]);
+        // b is covered by c.
+        for &covering in &[Covering::Gillies, Covering::Fishburn, Covering::McKelvey] {
+            assert_eq!(choose(TournamentSolution::UncoveredSet(covering)), vec![&"a", &"c", &"d", &"e"]);
+        }
+        assert_eq!(choose(TournamentSolution::Banks), vec![&"a", &"c", &"d", &"e"]);
+        assert_eq!(choose(TournamentSolution::MinimalCoveringSet), vec![&"c", &"d", &"e"]);
+        assert_eq!(choose(TournamentSolution::BipartisanSet), vec![&"c", &"d", &"e"]);
+
+        let vote = tournament(
+            &["a", "b", "c", "d", "e", "f"],
+            &[("a", "c"), ("a", "e"), ("a", "f"), ("b", "a"), ("b", "c"),
+              ("b", "f"), ("c", "d"), ("c", "f"), ("d", "a"), ("d", "b"),
+              ("e", "b"), ("e", "c"), ("e", "d"), ("f", "d"), ("f", "e")],
+        );
+        assert_eq!(TournamentSolution::MinimalCoveringSet.choose(&vote).unwrap().len(), 6);
+        assert_eq!(TournamentSolution::BipartisanSet.choose(&vote).unwrap(), vec![&"a", &"b", &"d", &"e", &"f"]);
+    }
+
+    #[test]
+    fn coverings_differ_on_ties() {
+        // a ties b and d.
+        let vote = tournament(&["a", "b", "c", "d"], &[("b", "c"), ("b", "d"), ("c", "a"), ("c", "d")]);
+        let uncovered = |covering| TournamentSolution::UncoveredSet(covering).choose(&vote).unwrap();
+        assert_eq!(uncovered(Covering::Gillies), vec![&"b", &"c"]);
+        assert_eq!(uncovered(Covering::Fishburn), vec![&"b"]);
+        assert_eq!(uncovered(Covering::McKelvey), vec![&"a", &"b", &"c"]);
+    }
+
+    #[test]
+    fn large_elections() {
+        // 5 beats every other candidate, the others all tie.
+        let vote = Vote::builder().candidates(0..64).ballot(1, vec![5]).build().unwrap();
+        for &solution in &[TournamentSolution::Banks, TournamentSolution::MinimalCoveringSet] {
+            assert_eq!(solution.choose(&vote), Some(vec![&5]));
+        }
+
+        // 5 and 6 tie, and beat every other candidate.
+        let vote = Vote::builder().candidates(0..64).ballot(1, vec![5]).ballot(1, vec![6]).build().unwrap();
+        for &solution in &[TournamentSolution::Banks, TournamentSolution::MinimalCoveringSet] {
+            assert_eq!(solution.choose(&vote), None);
+            assert_eq!(solution.elect(&vote).outcome, Outcome::Undecided);
+        }
+        assert_eq!(TournamentSolution::TopCycle.choose(&vote), Some(vec![&5, &6]));
+    }
+}
@@ -0,0 +1,174 @@
+use std::hash::Hash;
+
+use crate::{Decision, ElectionRule, OneStage, Outcome, Preference, Stake, Tally, TieBreak, TieBreaker, Vote};
+
+/// The two-round system: a plurality first round, won outright by a candidate
+/// holding more than `threshold` of the votes, otherwise followed by a runoff
+/// between the two leading candidates. Candidates tied for the most votes go
+/// to the runoff, even above the threshold.
+///
+/// Ballots are counted again for the runoff, going to whichever finalist they
+/// rank higher. Ballots ranking neither, or both equally, abstain.
+#[derive(Clone, Debug)]
+pub struct TwoRound<T> {
+    /// Share of the first-round votes needed to win outright, 0.5 for an
+    /// absolute majority, 0.4 for the 40% rule.
+    pub threshold: f64,
+    /// Settles ties for the runoff places and in the runoff itself.
+    pub tie_breaker: TieBreaker<T>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TwoRoundCount<'a, T> {
+    pub first_round: Tally<'a, T>,
+    /// Votes of both finalists, highest first, if there was a runoff.
+    pub runoff: Option<Vec<(&'a T, f64)>>,
+    /// `None` if nobody received a vote.
+    pub winner: Option<&'a T>,
+    pub tie_breaks: Vec<TieBreak<'a, T>>,
+}
+
+impl<T> TwoRound<T> {
+    /// Requires an absolute majority to win in the first round.
+    pub fn new(tie_breaker: TieBreaker<T>) -> Self {
+        TwoRound { threshold: 0.5, tie_breaker }
+    }
+
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+}
+
+impl<T: Eq + Hash + Clone> TwoRound<T> {
+    pub fn count<'a>(&self, vote: &'a Vote<T>) -> TwoRoundCount<'a, T> {
+        let first_round = vote.one_stage();
+        let mut count = TwoRoundCount { first_round, runoff: None, winner: None, tie_breaks: vec![] };
+        let total: f64 = count.first_round.scores.iter().map(|(_, score)| score).sum();
+        let (leader, best) = count.first_round.scores[0];
+        let alone = count.first_round.winners.len() == 1;
+        if count.first_round.scores.len() == 1 || (alone && total > 0. && best > self.threshold * total) {
+            count.winner = Some(leader);
+            return count;
+        }
+        if total == 0. {
+            return count;
+        }
+
+        let history = vec![count.first_round.scores.clone()];
+        let mut finalists = vec![];
+        let mut contenders = count.first_round.scores.clone();
+        while finalists.len() < 2 {
+            let top = contenders[0].1;
+            let tied: Vec<&T> = contenders.iter()
+                .take_while(|(_, score)| crate::same_score(*score, top))
+                .map(|&(c, _)| c)
+                .collect();
+            let qualified = if tied.len() <= 2 - finalists.len() {
+                tied
+            } else {
+                let tie_break = self.tie_breaker.break_tie(&tied, Stake::Win, &history);
+                let chosen = tie_break.chosen;
+                count.tie_breaks.push(tie_break);
+                vec![chosen]
+            };
+            contenders.retain(|(c, _)| !qualified.contains(c));
+            finalists.extend(qualified);
+        }
+
+        let (a, b) = (vote.id_of(finalists[0]).unwrap(), vote.id_of(finalists[1]).unwrap());
+        let mut votes = (0., 0.);
+        for (voters, ballot) in vote.ballots() {
+            match ballot.who_is_first(&a, &b) {
+                Preference::Above => votes.0 += *voters as f64,
+                Preference::Below => votes.1 += *voters as f64,
+                Preference::Indifferent | Preference::Incomparable => {}
+            }
+        }
+        let mut runoff = vec![(finalists[0], votes.0), (finalists[1], votes.1)];
+        runoff.sort_by(|x, y| y.1.partial_cmp(&x.1).unwrap());
+        count.winner = Some(if crate::same_score(votes.0, votes.1) {
+            let tie_break = self.tie_breaker.break_tie(&finalists, Stake::Win, &history);
+            let chosen = tie_break.chosen;
+            count.tie_breaks.push(tie_break);
+            chosen
+        } else {
+            runoff[0].0
+        });
+        count.runoff = Some(runoff);
+        count
+    }
+}
+
+impl<T: Eq + Hash + Clone> ElectionRule<T> for TwoRound<T> {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        let count = self.count(vote);
+        Decision {
+            outcome: count.winner.map_or(Outcome::NoWinner, Outcome::Winner),
+            scores: Some(count.runoff.unwrap_or(count.first_round.scores)),
+            tie_breaks: count.tie_breaks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{TieBreaker, TwoRound, Vote};
+
+    fn vote() -> Vote<&'static str> {
+        Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(42, vec!["a", "b"])
+            .ballot(35, vec!["b", "c"])
+            .ballot(23, vec!["c", "b", "a"])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn runoff_between_the_top_two() {
+        let vote = vote();
+        let count = TwoRound::new(TieBreaker::Lot(0)).count(&vote);
+        assert_eq!(count.first_round.scores[0], (&"a", 42.));
+        assert_eq!(count.runoff, Some(vec![(&"b", 58.), (&"a", 42.)]));
+        assert_eq!(count.winner, Some(&"b"));
+    }
+
+    #[test]
+    fn lower_threshold_wins_outright() {
+        let vote = vote();
+        let count = TwoRound::new(TieBreaker::Lot(0)).threshold(0.4).count(&vote);
+        assert_eq!(count.runoff, None);
+        assert_eq!(count.winner, Some(&"a"));
+    }
+
+    #[test]
+    fn tied_leaders_above_the_threshold_go_to_the_runoff() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(45, vec!["a"])
+            .ballot(45, vec!["b"])
+            .ballot(10, vec!["c"])
+            .build()
+            .unwrap();
+        let count = TwoRound::new(TieBreaker::Priority(vec!["b"])).threshold(0.4).count(&vote);
+        assert_eq!(count.runoff, Some(vec![(&"a", 45.), (&"b", 45.)]));
+        assert_eq!(count.tie_breaks[0].tied, vec![&"a", &"b"]);
+        assert_eq!(count.winner, Some(&"b"));
+    }
+
+    #[test]
+    fn ties_for_the_runoff() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c"])
+            .ballot(4, vec!["a"])
+            .ballot(3, vec!["b", "a"])
+            .ballot(3, vec!["c", "b"])
+            .build()
+            .unwrap();
+        let count = TwoRound::new(TieBreaker::Priority(vec!["c"])).count(&vote);
+        assert_eq!(count.tie_breaks[0].tied, vec![&"b", &"c"]);
+        assert_eq!(count.runoff, Some(vec![(&"a", 7.), (&"c", 3.)]));
+        assert_eq!(count.winner, Some(&"a"));
+    }
+}
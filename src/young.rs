@@ -0,0 +1,185 @@
+use std::hash::Hash;
+
+use crate::{CandidateId, Decision, ElectionRule, Outcome, Preference, PreOrder, SocialWelfareFunction, Vote};
+
+/// How Young scores are computed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum YoungMode {
+    /// Branch and bound over how many voters of each ballot group are
+    /// removed. Computing Young scores is NP-hard, so this is meant for small
+    /// elections, say a dozen ballot groups.
+    Exact,
+    /// Removes voters one at a time from whichever ballot group brings the
+    /// candidate closest to winning. An upper bound of the exact score, and
+    /// may give up on candidates who can win.
+    Greedy,
+}
+
+/// Young's rule: elects the candidates needing the fewest voters removed to
+/// become the Condorcet winner.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Young {
+    pub mode: YoungMode,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct YoungCount<'a, T> {
+    /// The voters to remove for every candidate, in declaration order, the
+    /// fewer the better. `None` if no removal makes it win.
+    pub removals: Vec<(&'a T, Option<usize>)>,
+    pub winners: Vec<&'a T>,
+}
+
+/// For each ballot group, its voters and how each of them counts in `x`'s
+/// contest against every candidate, indexed by id: 1 for `x`, -1 against.
+fn contests<T: Eq + Hash>(vote: &Vote<T>, x: CandidateId) -> Vec<(usize, Vec<i64>)> {
+    vote.ballots()
+        .iter()
+        .map(|(voters, ballot)| {
+            let sides = vote.ids()
+                .map(|y| match ballot.who_is_first(&x, &y) {
+                    Preference::Above => 1,
+                    Preference::Below => -1,
+                    Preference::Indifferent | Preference::Incomparable => 0,
+                })
+                .collect();
+            (*voters, sides)
+        })
+        .collect()
+}
+
+/// How far each of `x`'s margins, indexed by id, is from a win.
+fn needs(margins: &[i64], x: CandidateId) -> Vec<i64> {
+    margins.iter().enumerate().map(|(y, m)| if y == x.index() { 0 } else { (1 - m).max(0) }).collect()
+}
+
+fn search(groups: &[(usize, Vec<i64>)], potential: &[Vec<i64>], x: CandidateId, margins: Vec<i64>, cost: usize, best: &mut Option<usize>) {
+    let needs = needs(&margins, x);
+    // Every voter removed adds at most 1 to each margin.
+    let bound = *needs.iter().max().unwrap() as usize;
+    if bound == 0 {
+        *best = Some(best.map_or(cost, |b| b.min(cost)));
+        return;
+    }
+    if best.is_some_and(|b| cost + bound >= b) || groups.is_empty() {
+        return;
+    }
+    if needs.iter().zip(&potential[0]).any(|(need, p)| need > p) {
+        return;
+    }
+    let (voters, sides) = &groups[0];
+    for removed in (0..=*voters).rev() {
+        let margins = margins.iter().zip(sides).map(|(m, s)| m - removed as i64 * s).collect();
+        search(&groups[1..], &potential[1..], x, margins, cost + removed, best);
+    }
+}
+
+fn exact<T: Eq + Hash>(vote: &Vote<T>, x: CandidateId, margins: Vec<i64>) -> Option<usize> {
+    // Only ballots ranking someone above the candidate are worth removing.
+    let groups: Vec<_> = contests(vote, x).into_iter().filter(|(_, sides)| sides.contains(&-1)).collect();
+    let mut potential = vec![vec![0; margins.len()]; groups.len() + 1];
+    for g in (0..groups.len()).rev() {
+        let (voters, sides) = &groups[g];
+        potential[g] = sides.iter()
+            .zip(&potential[g + 1])
+            .map(|(s, p)| p + if *s < 0 { *voters as i64 } else { 0 })
+            .collect();
+    }
+    let mut best = None;
+    search(&groups, &potential, x, margins, 0, &mut best);
+    best
+}
+
+fn greedy<T: Eq + Hash>(vote: &Vote<T>, x: CandidateId, mut margins: Vec<i64>) -> Option<usize> {
+    let mut groups = contests(vote, x);
+    let mut removed = 0;
+    loop {
+        let missing: i64 = needs(&margins, x).iter().sum();
+        if missing == 0 {
+            return Some(removed);
+        }
+        let (g, after) = groups.iter()
+            .enumerate()
+            .filter(|(_, (voters, _))| *voters > 0)
+            .map(|(g, (_, sides))| {
+                let after: Vec<i64> = margins.iter().zip(sides).map(|(m, s)| m - s).collect();
+                (g, after)
+            })
+            .min_by_key(|(_, after)| needs(after, x).iter().sum::<i64>())?;
+        if needs(&after, x).iter().sum::<i64>() >= missing {
+            return None;
+        }
+        groups[g].0 -= 1;
+        margins = after;
+        removed += 1;
+    }
+}
+
+impl Young {
+    pub fn count<'a, T: Eq + Hash>(&self, vote: &'a Vote<T>) -> YoungCount<'a, T> {
+        let matrix = vote.pairwise_matrix();
+        let removals: Vec<(&T, Option<usize>)> = vote.ids()
+            .map(|x| {
+                let margins: Vec<i64> = vote.ids()
+                    .map(|y| matrix[[x.index(), y.index()]] as i64 - matrix[[y.index(), x.index()]] as i64)
+                    .collect();
+                let removals = match self.mode {
+                    YoungMode::Exact => exact(vote, x, margins),
+                    YoungMode::Greedy => greedy(vote, x, margins),
+                };
+                (vote.candidate(x), removals)
+            })
+            .collect();
+        let best = removals.iter().filter_map(|&(_, r)| r).min();
+        let winners = removals.iter().filter(|&&(_, r)| r.is_some() && r == best).map(|&(c, _)| c).collect();
+        YoungCount { removals, winners }
+    }
+}
+
+impl<T: Eq + Hash> ElectionRule<T> for Young {
+    fn elect<'a>(&self, vote: &'a Vote<T>) -> Decision<'a, T> {
+        Decision::new(Outcome::from_winners(self.count(vote).winners), None)
+    }
+}
+
+/// Ranks candidates from the fewest removals up, those who cannot win last.
+impl<T: Eq + Hash> SocialWelfareFunction<T> for Young {
+    fn rank<'a>(&self, vote: &'a Vote<T>) -> PreOrder<&'a T> {
+        let scores: Vec<(&T, f64)> = self.count(vote)
+            .removals
+            .into_iter()
+            .map(|(c, r)| (c, r.map_or(f64::MIN, |r| -(r as f64))))
+            .collect();
+        PreOrder::from_scores(&scores)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::tennessee;
+    use crate::{ElectionRule, Outcome, Vote, Young, YoungMode};
+
+    #[test]
+    fn young_removals() {
+        let vote = tennessee();
+        for &mode in &[YoungMode::Exact, YoungMode::Greedy] {
+            let removals: Vec<_> = Young { mode }.count(&vote).removals.into_iter().map(|(_, r)| r).collect();
+            assert_eq!(removals, vec![Some(17), Some(0), Some(37), Some(67)]);
+        }
+    }
+
+    #[test]
+    fn candidates_who_cannot_win() {
+        let vote = Vote::builder()
+            .candidates(vec!["a", "b", "c", "d"])
+            .ballot(1, vec!["c", "d", "b", "a"])
+            .ballot(1, vec!["c", "a", "b", "d"])
+            .ballot(2, vec!["b", "c", "d", "a"])
+            .build()
+            .unwrap();
+        let count = Young { mode: YoungMode::Exact }.count(&vote);
+        let removals: Vec<_> = count.removals.into_iter().map(|(_, r)| r).collect();
+        assert_eq!(removals, vec![None, Some(1), Some(1), None]);
+        assert_eq!(Young { mode: YoungMode::Exact }.elect(&vote).outcome, Outcome::Tie(vec![&"b", &"c"]));
+    }
+}